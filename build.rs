@@ -0,0 +1,45 @@
+//! Generates one test case per example file in `data/examples/NN/`, see `advent_of_code::example_tests!`.
+use std::path::Path;
+use std::{env, fs};
+
+const EXAMPLES_DIR: &str = "data/examples";
+
+/// Turns an example name into a valid identifier, e.g. `part-two` into `part_two`.
+fn test_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("example_{}", name.to_ascii_lowercase())
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={EXAMPLES_DIR}");
+
+    let out_dir = Path::new(&env::var("OUT_DIR").unwrap()).join("examples");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    for day in 1..=25 {
+        let mut names: Vec<String> =
+            fs::read_dir(Path::new(EXAMPLES_DIR).join(format!("{day:02}")))
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| {
+                    let path = entry.ok()?.path();
+                    if path.extension()? != "txt" {
+                        return None;
+                    }
+                    Some(path.file_stem()?.to_str()?.to_string())
+                })
+                .collect();
+        names.sort();
+
+        let cases: String = names
+            .iter()
+            .map(|name| format!("example_case!({}, {name:?});\n", test_name(name)))
+            .collect();
+
+        // every day gets a file, so that `include!` also works for days without examples.
+        fs::write(out_dir.join(format!("{day}.rs")), cases).unwrap();
+    }
+}
@@ -28,14 +28,15 @@ impl<T> Grid<T> {
     where
         F: Fn(char) -> T,
     {
-        let width = input.lines().next().unwrap().len();
+        // an empty input, e.g. a freshly scaffolded example, is an empty grid.
+        let width = input.lines().next().map_or(0, str::len);
         let content = input
             .chars()
             .filter(|&c| c != '\n')
             .map(map_char)
             .collect::<Vec<_>>();
 
-        let height = content.len() / width;
+        let height = content.len().checked_div(width).unwrap_or(0);
 
         Self {
             width,
@@ -344,6 +345,14 @@ mod tests {
         assert_eq!(grid.content[8], '9');
     }
 
+    #[test]
+    pub fn test_empty_grid() {
+        let grid = Grid::new_from_str("", &|c| c);
+        assert_eq!(grid.width, 0);
+        assert_eq!(grid.height, 0);
+        assert!(!grid.is_in_bounds(Point::new(0, 0)));
+    }
+
     #[test]
     pub fn test_grid_contains() {
         let grid = Grid::new_from_str("123\n456\n789", &|c| c);
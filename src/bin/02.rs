@@ -34,6 +34,7 @@ fn stream_input(input: &str) -> impl Iterator<Item = RangeInclusive<u64>> {
 }
 
 #[inline(never)]
+#[allow(clippy::unnecessary_cast)]
 pub fn part_one(input: &str) -> Option<u64> {
     let res = stream_input(input)
         .par_bridge()
@@ -48,6 +49,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 #[inline(never)]
+#[allow(clippy::unnecessary_cast)]
 pub fn part_two(input: &str) -> Option<u64> {
     let res = stream_input(input)
         .par_bridge()
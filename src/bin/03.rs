@@ -6,6 +6,7 @@ fn stream_input(input: &str) -> impl Iterator<Item = Vec<u8>> + '_ {
         .map(|line| line.chars().map(|c| c as u8 - b'0').collect())
 }
 
+#[allow(clippy::into_iter_on_ref)]
 fn solve<const N: usize>(line: &[u8]) -> u64 {
     let mut result = 0u64;
     let mut last_idx = 0usize;
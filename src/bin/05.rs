@@ -1,6 +1,8 @@
 use std::ops::RangeInclusive;
 
-advent_of_code::solution!(5);
+use advent_of_code::template::generate::Rng;
+
+advent_of_code::solution!(5, generate = generate);
 
 type IdRange = RangeInclusive<u64>;
 
@@ -62,10 +64,43 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(count)
 }
 
+/// `size` fresh ingredient ranges, which overlap and contain each other like in puzzle inputs,
+/// followed by five times as many ingredient IDs.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(1) as u64;
+    let max = size * 1_000_000_000;
+
+    let mut out = String::new();
+    for _ in 0..size {
+        let start = rng.range(1..max);
+        // ranges are wide enough to overlap a few others on average.
+        let end = (start + rng.below(4 * max / size)).min(max);
+        out.push_str(&format!("{start}-{end}\n"));
+    }
+
+    out.push('\n');
+    for _ in 0..5 * size {
+        out.push_str(&format!("{}\n", rng.range(1..max + 1)));
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_generated_inputs() {
+        for seed in 0..10 {
+            let input = generate(50, &mut Rng::new(seed));
+            let (ranges, numbers) = parse_input(&input);
+            assert_eq!((ranges.len(), numbers.count()), (50, 250));
+
+            let fresh = part_one(&input).unwrap();
+            assert!(0 < fresh && fresh < 250);
+        }
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
@@ -20,12 +20,11 @@ fn op_fold(acc: u64, item: u64, op: char) -> u64 {
 }
 
 #[inline(never)]
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut chars = input.chars();
+pub fn part_one(input: &str) -> Result<u64, String> {
     let mut data = Vec::with_capacity(1000);
     let mut operations = Vec::with_capacity(1000);
     let mut n = 0;
-    while let Some(c) = chars.next() {
+    for c in input.chars() {
         match c {
             '*' | '+' => operations.push(c),
             '0'..='9' => n = n * 10 + c.to_digit(10).unwrap() as u64,
@@ -34,7 +33,7 @@ pub fn part_one(input: &str) -> Option<u64> {
                 n = 0
             }
             ' ' | '\n' => (),
-            _ => unreachable!(),
+            _ => return Err(format!("unexpected character {c:?} in worksheet")),
         }
     }
     let grid = Grid::from_vec(data, operations.len());
@@ -47,17 +46,17 @@ pub fn part_one(input: &str) -> Option<u64> {
                 .fold(op_identity(op), |acc, &item| op_fold(acc, item, op))
         })
         .sum();
-    Some(total)
+    Ok(total)
 }
 
 #[inline(never)]
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: &str) -> Result<u64, String> {
     let mut nums = Vec::with_capacity(1000);
     let mut col_idx = 0;
     let mut chars = input.chars();
     let mut op = '+';
 
-    while let Some(c) = chars.next() {
+    for c in chars.by_ref() {
         match c {
             '*' | '+' => {
                 op = c;
@@ -79,17 +78,15 @@ pub fn part_two(input: &str) -> Option<u64> {
                     nums.push(n);
                 }
             }
-            _ => unreachable!(),
+            _ => return Err(format!("unexpected character {c:?} in worksheet")),
         }
         col_idx += 1;
     }
 
-    let mut op = op;
     let mut op_idx = 0;
-    let mut col = 0;
     let mut total = 0;
 
-    while let Some(c) = chars.next() {
+    for (col, c) in chars.enumerate() {
         match c {
             // TODO: dedup code but keep it fast
             '*' | '+' => {
@@ -105,15 +102,14 @@ pub fn part_two(input: &str) -> Option<u64> {
                     .iter()
                     .filter(|&x| *x != 0)
                     .fold(op_identity(op), |acc, &x| op_fold(acc, x, op));
-                return Some(total);
+                return Ok(total);
             }
             ' ' => (),
-            _ => unreachable!(),
+            _ => return Err(format!("unexpected character {c:?} in operators row")),
         }
-        col += 1;
     }
 
-    None
+    Err("operators row is not terminated by a newline".into())
 }
 
 #[cfg(test)]
@@ -123,12 +119,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4277556));
+        assert_eq!(result, Ok(4277556));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3263827));
+        assert_eq!(result, Ok(3263827));
     }
 }
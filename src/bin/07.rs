@@ -1,4 +1,24 @@
-advent_of_code::solution!(7);
+#![allow(
+    clippy::empty_line_after_doc_comments,
+    clippy::items_after_test_module,
+    clippy::missing_safety_doc,
+    clippy::while_let_on_iterator
+)]
+
+use advent_of_code::template::generate::Rng;
+use advent_of_code::unchecked::{Ptr, Unchecked};
+
+advent_of_code::solution!(7, generate = generate, variants = {
+    1: [part_one_safe, part_one_safe_col_skip, part_one_single_pass, part_one_raw_ptr, part_one_one_beams_array],
+    2: [part_two_safe, part_two_safe_col_skip, part_two_single_pass, part_two_raw_ptr, part_two_one_beams_array],
+}, assume = [
+    rows_of_equal_width,
+    start_on_first_row,
+    every_other_row_empty,
+    last_row_empty,
+    no_splitter_on_edge,
+    splitters_inside_triangle,
+]);
 
 #[allow(unused)]
 #[allow(unsafe_op_in_unsafe_fn)]
@@ -9,7 +29,7 @@ pub unsafe fn solve(input: &str) -> (usize, usize) {
     let mut start: usize = 0;
     loop {
         idx += 1;
-        match *bytes.get_unchecked(idx) {
+        match *bytes.at(idx) {
             b'S' => start = idx,
             b'\n' => break,
             _ => {}
@@ -22,7 +42,7 @@ pub unsafe fn solve(input: &str) -> (usize, usize) {
     let mut next_beams = vec![0_usize; width];
 
     let mut col = start;
-    *beams.get_unchecked_mut(col) = 1;
+    *beams.at_mut(col) = 1;
     idx += start + 1;
     // skip row 2
     idx += width + 1;
@@ -30,7 +50,7 @@ pub unsafe fn solve(input: &str) -> (usize, usize) {
     let mut col_skip: isize = start as isize - 1;
 
     while idx < len {
-        let c = *bytes.get_unchecked(idx);
+        let c = *bytes.at(idx);
         idx += 1;
 
         if c == b'\n' {
@@ -43,17 +63,17 @@ pub unsafe fn solve(input: &str) -> (usize, usize) {
             continue;
         }
 
-        let beam = *beams.get_unchecked(col);
+        let beam = *beams.at(col);
         if beam != 0 {
             match c {
                 b'^' => {
                     // input data is guaranteed to not have ^ at the edges
-                    *next_beams.get_unchecked_mut(col - 1) += beam;
-                    *next_beams.get_unchecked_mut(col + 1) += beam;
+                    *next_beams.at_mut(col - 1) += beam;
+                    *next_beams.at_mut(col + 1) += beam;
                     splits += 1;
                 }
                 b'.' => {
-                    *next_beams.get_unchecked_mut(col) += beam;
+                    *next_beams.at_mut(col) += beam;
                 }
                 _ => (),
             }
@@ -82,6 +102,66 @@ pub fn part_two(input: &str) -> Option<u64> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_generated_inputs() {
+        for seed in 0..10 {
+            let input = generate(20, &mut Rng::new(seed));
+            assert_eq!(rows_of_equal_width(&input), Ok(()));
+            assert_eq!(start_on_first_row(&input), Ok(()));
+            assert_eq!(every_other_row_empty(&input), Ok(()));
+            assert_eq!(last_row_empty(&input), Ok(()));
+            assert_eq!(no_splitter_on_edge(&input), Ok(()));
+            assert_eq!(splitters_inside_triangle(&input), Ok(()));
+
+            let (splits, timelines) = solve_safe(&input);
+            let expected = (splits as usize, timelines as usize);
+            unsafe {
+                assert_eq!(solve(&input), expected);
+                assert_eq!(solve_single_pass(&input), expected);
+                assert_eq!(solve_bounded_tree_single_pass_raw_ptr(&input), expected);
+                assert_eq!(
+                    solve_bounded_tree_single_pass_one_beams_array(&input),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_assumptions() {
+        let example = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(splitters_inside_triangle(&example), Ok(()));
+        assert_eq!(every_other_row_empty(&example), Ok(()));
+        assert_eq!(last_row_empty(&example), Ok(()));
+
+        let input = ".S.\n...\n.^.\n...\n..^\n...\n";
+        assert_eq!(rows_of_equal_width(input), Ok(()));
+        assert_eq!(start_on_first_row(input), Ok(()));
+        assert_eq!(
+            no_splitter_on_edge(input),
+            Err("row 5 has a splitter on its edge.".into())
+        );
+        assert_eq!(
+            every_other_row_empty(".S.\n.^.\n"),
+            Err("row 2 is not empty.".into())
+        );
+        assert_eq!(
+            last_row_empty(".S.\n...\n.^.\n"),
+            Err("found 3 rows, expected an empty row after the last row of splitters.".into())
+        );
+        assert_eq!(
+            splitters_inside_triangle("..S..\n.....\n.^...\n.....\n"),
+            Err("row 3 has a splitter in column 2, more than 0 columns away from `S`.".into())
+        );
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_checked_splitter_on_edge() {
+        unsafe { solve(".S.\n...\n.^.\n...\n..^\n...\n") };
+    }
+
     #[test]
     fn test_safe() {
         let result = solve_safe(&advent_of_code::template::read_file("examples", DAY));
@@ -126,6 +206,9 @@ mod tests {
         };
         assert_eq!(result, (21, 40));
     }
+
+    // reproducers saved by `diff 7`.
+    advent_of_code::example_tests!(7);
 }
 
 //////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -226,7 +309,7 @@ pub unsafe fn solve_single_pass(input: &str) -> (usize, usize) {
     let mut start: usize = 0;
     loop {
         idx += 1;
-        match *bytes.get_unchecked(idx) {
+        match *bytes.at(idx) {
             b'S' => start = idx,
             b'\n' => break,
             _ => {}
@@ -239,13 +322,13 @@ pub unsafe fn solve_single_pass(input: &str) -> (usize, usize) {
     let mut next_beams = vec![0_usize; width];
 
     let mut col = start;
-    *beams.get_unchecked_mut(col) = 1;
+    *beams.at_mut(col) = 1;
     idx += start + 1;
     // skip row 2
     idx += width + 1;
 
     while idx < len {
-        let c = *bytes.get_unchecked(idx);
+        let c = *bytes.at(idx);
         idx += 1;
 
         if c == b'\n' {
@@ -256,17 +339,17 @@ pub unsafe fn solve_single_pass(input: &str) -> (usize, usize) {
             continue;
         }
 
-        let beam = *beams.get_unchecked(col);
+        let beam = *beams.at(col);
         if beam != 0 {
             match c {
                 b'^' => {
                     // input data is guaranteed to not have ^ at the edges
-                    *next_beams.get_unchecked_mut(col - 1) += beam;
-                    *next_beams.get_unchecked_mut(col + 1) += beam;
+                    *next_beams.at_mut(col - 1) += beam;
+                    *next_beams.at_mut(col + 1) += beam;
                     splits += 1;
                 }
                 b'.' => {
-                    *next_beams.get_unchecked_mut(col) += beam;
+                    *next_beams.at_mut(col) += beam;
                 }
                 _ => (),
             }
@@ -282,37 +365,37 @@ pub unsafe fn solve_single_pass(input: &str) -> (usize, usize) {
 #[allow(unused)]
 #[allow(unsafe_op_in_unsafe_fn)]
 pub unsafe fn solve_bounded_tree_single_pass_raw_ptr(input: &str) -> (usize, usize) {
-    let len = input.len();
-    let init_ptr = input.as_ptr();
+    let init_ptr = Ptr::new(input.as_bytes());
 
     let mut ptr = init_ptr;
-    let stop = init_ptr.add(len);
+    let stop = init_ptr.end();
     let mut col: usize = 0;
     loop {
-        ptr = ptr.add(1);
-        match *ptr {
+        ptr = ptr.wrapping_add(1);
+        match *ptr.read() {
             b'S' => {
-                col = ptr as usize - init_ptr as usize;
+                col = ptr.offset();
             }
             b'\n' => break,
             _ => {}
         }
     }
 
-    let width = ptr as usize - init_ptr as usize;
+    let width = ptr.offset();
     let mut splits: usize = 0;
     let mut beams = vec![0_usize; width];
     let mut next_beams = vec![0_usize; width];
 
-    *beams.get_unchecked_mut(col) = 1;
+    *beams.at_mut(col) = 1;
     let mut col_skip: isize = col as isize - 1;
     ptr = ptr.wrapping_add(1 /* skip \n */);
     ptr = ptr.wrapping_add(width + 1 /* skip row 2 */);
     ptr = ptr.wrapping_add(col_skip as usize);
 
-    while ptr < stop {
+    // the pointer is moved before reading, so it must not reach `stop`.
+    while ptr.wrapping_add(1) < stop {
         ptr = ptr.wrapping_add(1);
-        let c = *ptr;
+        let c = *ptr.read();
 
         if c == b'\n' {
             ptr = ptr.wrapping_add(width + 1 /* one row is guaranteed to be empty */);
@@ -324,17 +407,17 @@ pub unsafe fn solve_bounded_tree_single_pass_raw_ptr(input: &str) -> (usize, usi
             continue;
         }
 
-        let beam = *beams.get_unchecked(col);
+        let beam = *beams.at(col);
         if beam != 0 {
             match c {
                 b'^' => {
                     // input data is guaranteed to not have ^ at the edges
-                    *next_beams.get_unchecked_mut(col - 1) += beam;
-                    *next_beams.get_unchecked_mut(col + 1) += beam;
+                    *next_beams.at_mut(col - 1) += beam;
+                    *next_beams.at_mut(col + 1) += beam;
                     splits += 1;
                 }
                 b'.' => {
-                    *next_beams.get_unchecked_mut(col) += beam;
+                    *next_beams.at_mut(col) += beam;
                 }
                 _ => (),
             }
@@ -356,7 +439,7 @@ pub unsafe fn solve_bounded_tree_single_pass_one_beams_array(input: &str) -> (us
     let mut start: usize = 0;
     loop {
         idx += 1;
-        match *bytes.get_unchecked(idx) {
+        match *bytes.at(idx) {
             b'S' => start = idx,
             b'\n' => break,
             _ => {}
@@ -373,12 +456,12 @@ pub unsafe fn solve_bounded_tree_single_pass_one_beams_array(input: &str) -> (us
     idx += width + 1; // skip 1 row
     idx += start; // skip to start 
     let mut beam_idx = idx % (width + 1);
-    *beams.get_unchecked_mut(beam_idx) = 1;
+    *beams.at_mut(beam_idx) = 1;
 
     let mut col_skip: isize = start as isize - 1;
 
     while idx < len {
-        let c = *bytes.get_unchecked(idx);
+        let c = *bytes.at(idx);
         idx += 1;
 
         if c == b'\n' {
@@ -390,19 +473,19 @@ pub unsafe fn solve_bounded_tree_single_pass_one_beams_array(input: &str) -> (us
             continue;
         }
 
-        let beam = *beams.get_unchecked(beam_idx);
+        let beam = *beams.at(beam_idx);
         beam_idx += 1;
 
         if beam != 0 {
             match c {
                 b'^' => {
                     // input data is guaranteed to not have ^ at the edges
-                    *beams.get_unchecked_mut(beam_idx + width - 1) += beam;
-                    *beams.get_unchecked_mut(beam_idx + width + 1) += beam;
+                    *beams.at_mut(beam_idx + width - 1) += beam;
+                    *beams.at_mut(beam_idx + width + 1) += beam;
                     splits += 1;
                 }
                 b'.' => {
-                    *beams.get_unchecked_mut(beam_idx + width) += beam;
+                    *beams.at_mut(beam_idx + width) += beam;
                 }
                 _ => (),
             }
@@ -413,3 +496,172 @@ pub unsafe fn solve_bounded_tree_single_pass_one_beams_array(input: &str) -> (us
 
     (splits, timelines)
 }
+
+//////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+///
+///  Input assumptions, which the unsafe solutions rely on
+///
+//////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+fn rows_of_equal_width(input: &str) -> Result<(), String> {
+    let width = input.lines().next().map_or(0, str::len);
+    match input.lines().position(|line| line.len() != width) {
+        Some(row) => Err(format!(
+            "row {} is not {width} wide like the first row.",
+            row + 1
+        )),
+        None => Ok(()),
+    }
+}
+
+fn start_on_first_row(input: &str) -> Result<(), String> {
+    let first = input.lines().next().unwrap_or_default();
+    if first.matches('S').count() != 1 {
+        return Err("the first row does not have exactly one `S`.".into());
+    }
+    match input.lines().skip(1).position(|line| line.contains('S')) {
+        Some(row) => Err(format!("row {} has an `S` as well.", row + 2)),
+        None => Ok(()),
+    }
+}
+
+fn every_other_row_empty(input: &str) -> Result<(), String> {
+    match input
+        .lines()
+        .enumerate()
+        .skip(1)
+        .step_by(2)
+        .find(|(_, line)| line.bytes().any(|b| b != b'.'))
+    {
+        Some((row, _)) => Err(format!("row {} is not empty.", row + 1)),
+        None => Ok(()),
+    }
+}
+
+/// Together with `every_other_row_empty`, the input ends with an empty row, which the one beams array solution
+/// reads past the last row of splitters.
+fn last_row_empty(input: &str) -> Result<(), String> {
+    match input.lines().count() {
+        rows if rows % 2 == 0 => Ok(()),
+        rows => Err(format!(
+            "found {rows} rows, expected an empty row after the last row of splitters."
+        )),
+    }
+}
+
+fn no_splitter_on_edge(input: &str) -> Result<(), String> {
+    match input
+        .lines()
+        .position(|line| line.starts_with('^') || line.ends_with('^'))
+    {
+        Some(row) => Err(format!("row {} has a splitter on its edge.", row + 1)),
+        None => Ok(()),
+    }
+}
+
+/// The splitters of the `k`-th splitter row are at most `k` columns away from `S`, so that beams never reach
+/// the columns skipped by the bounded tree solutions.
+fn splitters_inside_triangle(input: &str) -> Result<(), String> {
+    let start = input
+        .lines()
+        .next()
+        .and_then(|line| line.find('S'))
+        .unwrap_or(0);
+    for (row, line) in input.lines().enumerate().skip(2).step_by(2) {
+        let k = row / 2 - 1;
+        if let Some(col) = line
+            .match_indices('^')
+            .map(|(col, _)| col)
+            .find(|col| col.abs_diff(start) > k)
+        {
+            return Err(format!(
+                "row {} has a splitter in column {}, more than {k} columns away from `S`.",
+                row + 1,
+                col + 1
+            ));
+        }
+    }
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+///
+///  Variants, compared with `part_one` and `part_two` by `cargo run -- diff 7`.
+///  `diff` builds with the `checked` feature, so mutated inputs (`--mutate`) that break the requirements
+///  of the unsafe solutions make them panic instead of reading out of bounds.
+///
+//////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+fn part_one_safe(input: &str) -> Option<u64> {
+    Some(solve_safe(input).0)
+}
+
+fn part_two_safe(input: &str) -> Option<u64> {
+    Some(solve_safe(input).1)
+}
+
+fn part_one_safe_col_skip(input: &str) -> Option<u64> {
+    Some(solve_safe_col_skip(input).0)
+}
+
+fn part_two_safe_col_skip(input: &str) -> Option<u64> {
+    Some(solve_safe_col_skip(input).1)
+}
+
+fn part_one_single_pass(input: &str) -> Option<usize> {
+    Some(unsafe { solve_single_pass(input) }.0)
+}
+
+fn part_two_single_pass(input: &str) -> Option<usize> {
+    Some(unsafe { solve_single_pass(input) }.1)
+}
+
+fn part_one_raw_ptr(input: &str) -> Option<usize> {
+    Some(unsafe { solve_bounded_tree_single_pass_raw_ptr(input) }.0)
+}
+
+fn part_two_raw_ptr(input: &str) -> Option<usize> {
+    Some(unsafe { solve_bounded_tree_single_pass_raw_ptr(input) }.1)
+}
+
+fn part_one_one_beams_array(input: &str) -> Option<usize> {
+    Some(unsafe { solve_bounded_tree_single_pass_one_beams_array(input) }.0)
+}
+
+fn part_two_one_beams_array(input: &str) -> Option<usize> {
+    Some(unsafe { solve_bounded_tree_single_pass_one_beams_array(input) }.1)
+}
+
+//////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+///
+///  Input generator
+///
+//////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A manifold with `size` rows of splitters. Like puzzle inputs, splitters only sit inside the triangle below
+/// `S`, on every other column, so none of them is on an edge, and every other row is empty.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let rows = size.max(1);
+    let width = 2 * rows + 1;
+    let start = rows;
+    let empty = ".".repeat(width);
+
+    let mut lines = vec![];
+    let mut first = empty.clone().into_bytes();
+    first[start] = b'S';
+    lines.push(String::from_utf8(first).unwrap());
+    lines.push(empty.clone());
+
+    for row in 0..rows {
+        let mut line = empty.clone().into_bytes();
+        for col in (start - row..=start + row).step_by(2) {
+            if rng.chance(0.6) {
+                line[col] = b'^';
+            }
+        }
+        lines.push(String::from_utf8(line).unwrap());
+        lines.push(empty.clone());
+    }
+
+    lines.join("\n") + "\n"
+}
@@ -5,7 +5,24 @@ use kdtree::distance::squared_euclidean;
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 
-advent_of_code::solution!(8);
+use advent_of_code::template::context::Context;
+
+advent_of_code::solution!(
+    8,
+    params = { connections: 1000 },
+    example = { connections: 10 },
+    assume = [at_most_1000_points]
+);
+
+/// The disjoint sets are stored in an array of this size.
+const MAX_POINTS: usize = 1000;
+
+fn at_most_1000_points(input: &str) -> Result<(), String> {
+    match input.lines().count() {
+        n if n <= MAX_POINTS => Ok(()),
+        n => Err(format!("found {n} points.")),
+    }
+}
 
 fn parse_input(input: &str) -> (Vec<[f64; 3]>, impl Iterator<Item = (usize, usize)>) {
     let points = input
@@ -39,11 +56,12 @@ fn parse_input(input: &str) -> (Vec<[f64; 3]>, impl Iterator<Item = (usize, usiz
     (points, entries.into_iter_sorted().map(|r| (r.0.1, r.0.2)))
 }
 
-fn solve_p1<const CONNECTIONS: usize>(input: &str) -> u64 {
+#[allow(clippy::needless_return)]
+fn solve_p1(input: &str, connections: usize) -> u64 {
     let (points, entries) = parse_input(input);
 
-    let mut dsu = aph_disjoint_set::DisjointSetArrayU16::<1000>::new();
-    entries.take(CONNECTIONS).for_each(|(a, b)| {
+    let mut dsu = aph_disjoint_set::DisjointSetArrayU16::<MAX_POINTS>::new();
+    entries.take(connections).for_each(|(a, b)| {
         dsu.union(a, b);
     });
 
@@ -59,10 +77,11 @@ fn solve_p1<const CONNECTIONS: usize>(input: &str) -> u64 {
     return circuits[0..3].iter().product::<u64>();
 }
 
+#[allow(clippy::needless_return)]
 fn solve_p2(input: &str) -> u64 {
     let (points, entries) = parse_input(input);
 
-    let mut dsu = aph_disjoint_set::DisjointSetArrayU16::<1000>::new();
+    let mut dsu = aph_disjoint_set::DisjointSetArrayU16::<MAX_POINTS>::new();
     let (a, b) = entries
         .into_iter()
         // .take(5 * points.len()) // empirical cutoff
@@ -74,8 +93,8 @@ fn solve_p2(input: &str) -> u64 {
 }
 
 #[inline(never)]
-pub fn part_one(input: &str) -> Option<u64> {
-    Some(solve_p1::<1000>(input))
+pub fn part_one(input: &str, ctx: &Context) -> Option<u64> {
+    Some(solve_p1(input, ctx.params.get("connections")))
 }
 
 #[inline(never)]
@@ -90,8 +109,18 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::template::read_file("examples", DAY);
-        let result = solve_p1::<10>(&input);
-        assert_eq!(result, 40);
+        let result = part_one(&input, &context("examples"));
+        assert_eq!(result, Some(40));
+    }
+
+    #[test]
+    fn test_assumptions() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(at_most_1000_points(&input), Ok(()));
+        assert_eq!(
+            at_most_1000_points(&"1,2,3\n".repeat(1001)),
+            Err("found 1001 points.".into())
+        );
     }
 
     #[test]
@@ -1,6 +1,14 @@
+#![allow(clippy::empty_line_after_doc_comments)]
+
+use std::f64::consts::PI;
+
+use advent_of_code::template::generate::Rng;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, generate = generate, variants = {
+    1: [part_one_brute],
+    2: [part_two_brute],
+}, assume = [circle_with_horizontal_cut]);
 
 ////////////////////////////////////////////////////////////////
 ///  Helper functions
@@ -215,6 +223,121 @@ pub fn part_two_cheated(input: &str) -> Option<i64> {
     Some(max_area)
 }
 
+/// The cheated solutions assume that the tiles lie on a circle, except for the two tiles at the end of a
+/// horizontal cut towards the center. This holds for real inputs, not for the example.
+fn circle_with_horizontal_cut(input: &str) -> Result<(), String> {
+    let pos = input
+        .lines()
+        .map(|line| {
+            let (x, y) = line.split_once(',')?;
+            Some((x.parse::<i64>().ok()?, y.parse::<i64>().ok()?))
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or("expected a tile `x,y` on every line.")?;
+    if pos.len() < 4 {
+        return Err(format!("expected at least 4 tiles, got {}.", pos.len()));
+    }
+
+    let [xmin, xmax, ymin, ymax] = pos.iter().fold(
+        [f64::MAX, f64::MIN, f64::MAX, f64::MIN],
+        |[xmin, xmax, ymin, ymax], &(x, y)| {
+            let (x, y) = (x as f64, y as f64);
+            [xmin.min(x), xmax.max(x), ymin.min(y), ymax.max(y)]
+        },
+    );
+    let center = ((xmin + xmax) / 2.0, (ymin + ymax) / 2.0);
+    let radius = (xmax - xmin).max(ymax - ymin) / 2.0;
+    let distance = |(x, y): (i64, i64)| (x as f64 - center.0).hypot(y as f64 - center.1) / radius;
+
+    // tiles of the staircase around the circle are slightly inside of it.
+    let inside: Vec<usize> = (0..pos.len()).filter(|&i| distance(pos[i]) < 0.9).collect();
+    if inside.len() > 2 {
+        return Err(format!(
+            "{} tiles are not on the circle, e.g. {},{}.",
+            inside.len(),
+            pos[inside[0]].0,
+            pos[inside[0]].1
+        ));
+    }
+
+    for &i in &inside {
+        let neighbours = [
+            pos[(i + pos.len() - 1) % pos.len()],
+            pos[(i + 1) % pos.len()],
+        ];
+        if !neighbours
+            .iter()
+            .any(|n| n.1 == pos[i].1 && distance(*n) >= 0.9)
+        {
+            let (x, y) = pos[i];
+            return Err(format!(
+                "tile {x},{y} is not on the circle, and not joined to it by a horizontal edge."
+            ));
+        }
+    }
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////
+///  Input generator
+///     A circle with a horizontal cut from its left edge past
+///     the center, the shape the cheated solutions assume
+////////////////////////////////////////////////////////////////
+
+/// A polygon of about `size` red tiles, listed counterclockwise. Smaller sizes still give about 400 tiles with
+/// coordinates up to about 100000 like real inputs, as the cheated solutions use fixed cutoffs.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let steps = (size / 2).max(200);
+    let radius = 48_000.max(steps as i64 * 100) + rng.range(0..1000) as i64;
+    let center = radius + rng.range(500..2000) as i64;
+    // half the height of the cut, and how far its tip is right of the center.
+    let half_height = rng.range(200..1000) as i64;
+    let depth = radius * rng.range(45..65) as i64 / 100;
+
+    let at = |angle: f64| {
+        let (sin, cos) = angle.sin_cos();
+        (
+            (center as f64 + radius as f64 * cos).round() as i64,
+            (center as f64 + radius as f64 * sin).round() as i64,
+        )
+    };
+
+    // the cut leaves the circle at these angles, below and above its leftmost point.
+    let opening = (half_height as f64 / radius as f64).asin();
+    let (first, last) = (PI + opening, 3.0 * PI - opening);
+    let step = (last - first) / steps as f64;
+
+    let start = (at(first).0, center - half_height);
+    let end = (at(last).0, center + half_height);
+
+    // tiles on the circle, each one left with a vertical edge. The first one shares the column of `start`.
+    let mut ring = vec![(start.0, at(first + step).1)];
+    for i in 2..steps {
+        let jitter = (rng.below(1000) as f64 / 1000.0 - 0.5) * step / 2.0;
+        let tile = at(first + i as f64 * step + jitter);
+        let previous = ring[ring.len() - 1];
+        // tiles sharing a row or a column with the previous one would not need a corner between them. No tile
+        // lies in the rows of the cut, as the cheated solutions do not check rectangles against its edges.
+        if tile.0 != previous.0 && tile.1 != previous.1 && (tile.1 < start.1 || tile.1 > end.1) {
+            ring.push(tile);
+        }
+    }
+    while ring.len() > 1 && (ring[ring.len() - 1].0 == end.0 || ring[ring.len() - 1].1 == end.1) {
+        ring.pop();
+    }
+    ring.push(end);
+
+    let mut tiles = vec![start, ring[0]];
+    for pair in ring.windows(2) {
+        tiles.push((pair[1].0, pair[0].1));
+        tiles.push(pair[1]);
+    }
+    tiles.push((center + depth, end.1));
+    tiles.push((center + depth, start.1));
+
+    tiles.iter().map(|(x, y)| format!("{x},{y}\n")).collect()
+}
+
 ////////////////////////////////////////////////////////////////
 ///  Entry point
 ////////////////////////////////////////////////////////////////
@@ -233,6 +356,48 @@ pub fn part_two(input: &str) -> Option<i64> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_generated_inputs() {
+        for seed in 0..3 {
+            let input = generate(100, &mut Rng::new(seed));
+            let tiles = parse_input(&input);
+            assert!(tiles.len() >= 390, "{input}");
+
+            // consecutive tiles share a row or a column, alternating.
+            for (i, a) in tiles.iter().enumerate() {
+                let b = tiles[(i + 1) % tiles.len()];
+                let c = tiles[(i + 2) % tiles.len()];
+                assert!((a.0 == b.0) != (b.0 == c.0), "{input}");
+                assert!((a.0 == b.0) != (a.1 == b.1), "{input}");
+            }
+            assert_eq!(circle_with_horizontal_cut(&input), Ok(()));
+            assert_eq!(part_one_cheated(&input), part_one_brute(&input));
+            assert_eq!(part_two_cheated(&input), part_two_brute(&input));
+        }
+    }
+
+    #[test]
+    fn test_assumptions() {
+        let example = advent_of_code::template::read_file("examples", DAY);
+        assert!(circle_with_horizontal_cut(&example).is_err());
+        assert!(circle_with_horizontal_cut("1,2\n3\n").is_err());
+        assert!(circle_with_horizontal_cut("").is_err());
+
+        // a cut from the left edge to the center, then around a circle of radius 100.
+        let mut tiles = vec![(0, 97), (100, 97), (100, 103), (0, 103)];
+        tiles.extend((1..40).map(|step| {
+            let angle = std::f64::consts::PI * (1.0 + step as f64 / 20.0);
+            let (x, y) = (100.0 + 100.0 * angle.cos(), 100.0 + 100.0 * angle.sin());
+            (x.round() as i64, y.round() as i64)
+        }));
+        let input: String = tiles.iter().map(|(x, y)| format!("{x},{y}\n")).collect();
+        assert_eq!(circle_with_horizontal_cut(&input), Ok(()));
+        assert_eq!(
+            circle_with_horizontal_cut(&(input + "150,150\n")),
+            Err("3 tiles are not on the circle, e.g. 100,97.".into())
+        );
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one_brute(&advent_of_code::template::read_file("examples", DAY));
@@ -244,4 +409,7 @@ mod tests {
         let result = part_two_brute(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(24));
     }
+
+    // reproducers saved by `diff 9`.
+    advent_of_code::example_tests!(9);
 }
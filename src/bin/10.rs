@@ -1,7 +1,9 @@
 #![feature(slice_partition_dedup)]
+#![allow(unused_features, clippy::empty_line_after_doc_comments)]
 
 use std::collections::VecDeque;
 
+use advent_of_code::template::context::Context;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use rustc_hash::FxHashSet;
 use z3::{Optimize, SatResult, ast::Int};
@@ -14,41 +16,52 @@ advent_of_code::solution!(10);
 
 type Lights = u16;
 
+/// The text between `open` and `close`, e.g. the lights between "[" and "]".
 #[inline(always)]
-fn parse_schematics(schematics: &str) -> Lights {
-    let sch = &schematics[1..(schematics.len() - 1)]; // skip "[" and "]"
+fn enclosed(s: &str, open: char, close: char) -> Result<&str, String> {
+    s.strip_prefix(open)
+        .and_then(|s| s.strip_suffix(close))
+        .ok_or_else(|| format!("expected {s:?} to be enclosed in {open}{close}"))
+}
+
+#[inline(always)]
+fn parse_schematics(schematics: &str) -> Result<Lights, String> {
+    let sch = enclosed(schematics, '[', ']')?;
+    if sch.len() > Lights::BITS as usize {
+        return Err(format!(
+            "expected at most {} lights, got {schematics}",
+            Lights::BITS
+        ));
+    }
     sch.chars()
         .map(|c| match c {
-            '.' => 0,
-            '#' => 1,
-            _ => unreachable!(),
+            '.' => Ok(0),
+            '#' => Ok(1),
+            _ => Err(format!("unexpected character {c:?} in lights {schematics}")),
         })
         .enumerate()
-        .fold(0_u16, |acc, (i, x)| acc | (x << (i as u64)))
+        .try_fold(0_u16, |acc, (i, x)| Ok(acc | (x? << (i as u64))))
 }
 
 #[inline(always)]
-fn parse_button_p1(button: &str) -> Lights {
-    button[1..(button.len() - 1)] // skip "(" and ")"
+fn parse_button_p1(button: &str) -> Result<Lights, String> {
+    enclosed(button, '(', ')')?
         .split(',')
-        .map(|n| n.parse::<u64>().unwrap())
-        .fold(0_u16, |acc, x| acc | (1 << x as u16))
-}
-
-#[inline(always)]
-#[allow(unused)]
-fn debug_lights(lights: Lights) {
-    println!("{:016b}", lights);
+        .map(|n| match n.parse::<u32>() {
+            Ok(x) if x < Lights::BITS => Ok(x),
+            _ => Err(format!("unexpected light {n:?} in button {button}")),
+        })
+        .try_fold(0_u16, |acc, x| Ok(acc | (1 << x?)))
 }
 
 #[inline(always)]
-fn bfs_p1(sch: Lights, buttons: &[Lights]) -> u64 {
+fn bfs_p1(sch: Lights, buttons: &[Lights]) -> Option<u64> {
     let mut visited = FxHashSet::with_capacity_and_hasher(1000, Default::default());
     let mut q = VecDeque::with_capacity(1000);
     q.push_back((0, 0));
     while let Some((lights, depth)) = q.pop_front() {
         if lights == sch {
-            return depth;
+            return Some(depth);
         }
         if visited.contains(&lights) {
             continue;
@@ -59,28 +72,33 @@ fn bfs_p1(sch: Lights, buttons: &[Lights]) -> u64 {
         }
     }
 
-    unreachable!("As per the problem statement, there is always a solution");
+    // as per the problem statement, there is always a solution.
+    None
 }
 
 #[inline(never)]
-pub fn part_one(input: &str) -> Option<u64> {
-    let res = input
+pub fn part_one(input: &str, ctx: &Context) -> Result<u64, String> {
+    let presses = input
         .lines()
         .take_while(|line| !line.is_empty())
         .par_bridge()
         .map(|line| {
             let mut parts = line.split(' ');
-            let sch = parse_schematics(parts.next().unwrap());
+            let sch = parse_schematics(parts.next().unwrap_or_default())?;
             let buttons = parts
                 .take_while(|b| b.starts_with('('))
                 .map(parse_button_p1)
-                .collect::<Vec<_>>();
+                .collect::<Result<Vec<_>, _>>()?;
 
-            bfs_p1(sch, &buttons)
+            let presses = bfs_p1(sch, &buttons)
+                .ok_or_else(|| format!("no buttons turn on the lights of {line:?}"))?;
+            advent_of_code::debug!(ctx, "{sch:016b}: {presses} presses");
+            Ok::<_, String>((presses, 1))
         })
-        .sum();
+        .try_reduce(|| (0, 0), |a, b| Ok((a.0 + b.0, a.1 + b.1)))?;
 
-    Some(res)
+    ctx.record("machines", presses.1);
+    Ok(presses.0)
 }
 
 ////////////////////////////////////////////////////////////
@@ -92,26 +110,36 @@ const MAX_JOLTS: usize = 12;
 type Jolts = [u16; MAX_JOLTS];
 
 #[inline(always)]
-fn parse_button_p2(button: &str) -> Jolts {
+fn parse_button_p2(button: &str) -> Result<Jolts, String> {
     let mut jolts = [0; MAX_JOLTS];
-    for jolt in button[1..(button.len() - 1)].split(',') {
-        let idx = jolt.parse::<u8>().unwrap() as usize;
-        jolts[idx] = 1;
+    for jolt in enclosed(button, '(', ')')?.split(',') {
+        match jolt.parse::<usize>() {
+            Ok(idx) if idx < MAX_JOLTS => jolts[idx] = 1,
+            _ => return Err(format!("unexpected counter {jolt:?} in button {button}")),
+        }
     }
-    jolts
+    Ok(jolts)
 }
 
 #[inline(always)]
-fn parse_counters(counter: &str) -> Jolts {
+fn parse_counters(counter: &str) -> Result<Jolts, String> {
     let mut jolts = [0; MAX_JOLTS];
-    for (i, jolt) in counter[1..(counter.len() - 1)].split(',').enumerate() {
-        jolts[i] = jolt.parse::<u16>().unwrap();
+    let values = enclosed(counter, '{', '}')?.split(',');
+    for (i, jolt) in values.enumerate() {
+        if i == MAX_JOLTS {
+            return Err(format!(
+                "expected at most {MAX_JOLTS} counters, got {counter}"
+            ));
+        }
+        jolts[i] = jolt
+            .parse::<u16>()
+            .map_err(|e| format!("unexpected counter {jolt:?} in {counter}: {e}"))?;
     }
-    jolts
+    Ok(jolts)
 }
 
 #[inline(always)]
-fn solve_z3(counters: Jolts, buttons: &[Jolts]) -> u64 {
+fn solve_z3(counters: Jolts, buttons: &[Jolts]) -> Result<u64, String> {
     // B: inverse of buttons matrix (MAX_JOLT x N_buttons)
     // C: counters column matrix (MAX_JOLT x 1)
     // X: presses matrix (N_buttons x 1)
@@ -160,30 +188,29 @@ fn solve_z3(counters: Jolts, buttons: &[Jolts]) -> u64 {
     match opt.check(&[]) {
         SatResult::Sat => {
             let model = opt.get_model().unwrap();
-            let min_total = model.eval(&total, true).unwrap().as_i64().unwrap() as u64;
-            min_total
+            Ok(model.eval(&total, true).unwrap().as_i64().unwrap() as u64)
         }
-        _ => panic!("unsat or unknown"),
+        result => Err(format!("z3 could not find a solution: {result:?}")),
     }
 }
 
 #[inline(never)]
-pub fn part_two(input: &str) -> Option<u64> {
-    let res = input
+pub fn part_two(input: &str) -> Result<u64, String> {
+    input
         .lines()
         .take_while(|line| !line.is_empty())
         .par_bridge()
         .map(|line| {
             let mut parts = line.split(' ').skip(1).collect::<Vec<_>>(); // skip lights
-            let counters = parse_counters(parts.remove(parts.len() - 1));
-            let buttons = parts.into_iter().map(parse_button_p2).collect::<Vec<_>>();
+            let counters = parse_counters(parts.pop().unwrap_or_default())?;
+            let buttons = parts
+                .into_iter()
+                .map(parse_button_p2)
+                .collect::<Result<Vec<_>, _>>()?;
 
-            let res = solve_z3(counters, &buttons);
-            res
+            solve_z3(counters, &buttons)
         })
-        .sum();
-
-    Some(res)
+        .try_reduce(|| 0, |a, b| Ok(a + b))
 }
 
 #[cfg(test)]
@@ -192,13 +219,29 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY),
+            &context("examples"),
+        );
+        assert_eq!(result, Ok(7));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let ctx = context("examples");
+        assert_eq!(part_one("[.#] (1) {1,1}\n", &ctx), Ok(1));
+        assert!(part_one("[.#\n", &ctx).is_err());
+        assert!(part_one("[.x] (1) {1,1}\n", &ctx).is_err());
+        assert!(part_one("[.#] (16) {1,1}\n", &ctx).is_err());
+        assert!(part_one("[.#] (0) {1,1}\n", &ctx).is_err());
+        assert!(part_two("[.#] (1) {1,1\n").is_err());
+        assert!(part_two("[.#] (12) {1,1}\n").is_err());
+        assert!(part_two("[.#] (1) {1,1,1,1,1,1,1,1,1,1,1,1,1}\n").is_err());
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(33));
+        assert_eq!(result, Ok(33));
     }
 }
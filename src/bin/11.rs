@@ -1,14 +1,20 @@
 use std::collections::VecDeque;
 
-advent_of_code::solution!(11);
+use advent_of_code::template::generate::Rng;
+
+advent_of_code::solution!(11, generate = generate);
 
 #[inline(always)]
-fn hash(node: &str) -> usize {
-    debug_assert_eq!(node.len(), 3);
+fn hash(node: &str) -> Result<usize, String> {
+    if node.len() != 3 || !node.bytes().all(|c| c.is_ascii_lowercase()) {
+        return Err(format!(
+            "expected a name of 3 lowercase letters, got {node:?}"
+        ));
+    }
     let node = node.as_bytes();
-    (((node[0] - b'a') as usize) << 10
+    Ok(((node[0] - b'a') as usize) << 10
         | ((node[1] - b'a') as usize) << 5
-        | ((node[2] - b'a') as usize)) as usize
+        | ((node[2] - b'a') as usize))
 }
 
 #[allow(unused)]
@@ -21,20 +27,25 @@ fn node_id_to_str(node: usize) -> String {
     s
 }
 
-fn parse_input(input: &str) -> (Vec<usize>, Vec<Vec<usize>>) {
+#[allow(clippy::repeat_vec_with_capacity)]
+fn parse_input(input: &str) -> Result<(Vec<usize>, Vec<Vec<usize>>), String> {
     let mut nodes = Vec::with_capacity(1 << 15);
     let mut edges = vec![Vec::with_capacity(10); 1 << 15]; // wasteful in memory but faster than FxHashMap
 
     for line in input.lines() {
-        let (n, e) = line.split_once(':').unwrap();
-        let ni = hash(n);
+        let (n, e) = line
+            .split_once(':')
+            .ok_or_else(|| format!("expected `node: outputs`, got {line:?}"))?;
+        let ni = hash(n)?;
         nodes.push(ni);
 
         debug_assert!(edges[ni].is_empty());
-        edges[ni].extend(e.split_whitespace().map(hash));
+        for e in e.split_whitespace() {
+            edges[ni].push(hash(e)?);
+        }
     }
 
-    (nodes, edges)
+    Ok((nodes, edges))
 }
 
 fn topological_sort(nodes: &[usize], edges: &[Vec<usize>]) -> Vec<usize> {
@@ -80,57 +91,155 @@ fn count_ways(ordered_nodes: &[usize], edges: &[Vec<usize>], start: usize, end:
 }
 
 #[inline(never)]
-pub fn part_one(input: &str) -> Option<u64> {
-    let (nodes, edges) = parse_input(input);
+pub fn part_one(input: &str) -> Result<u64, String> {
+    let (nodes, edges) = parse_input(input)?;
 
     let ordered = topological_sort(&nodes, &edges);
 
-    Some(count_ways(&ordered, &edges, hash("you"), hash("out")))
+    Ok(count_ways(&ordered, &edges, hash("you")?, hash("out")?))
 }
 
 #[inline(never)]
-pub fn part_two(input: &str) -> Option<u64> {
-    let (nodes, edges) = parse_input(input);
+pub fn part_two(input: &str) -> Result<u64, String> {
+    let (nodes, edges) = parse_input(input)?;
     let ordered = topological_sort(&nodes, &edges);
 
-    let [svr, dac, fft, out] = [hash("svr"), hash("dac"), hash("fft"), hash("out")];
+    let [svr, dac, fft, out] = [hash("svr")?, hash("dac")?, hash("fft")?, hash("out")?];
     let ways = |from: usize, to: usize| count_ways(&ordered, &edges, from, to);
 
     match (ways(dac, fft), ways(fft, dac)) {
-        (dac_fft, 0) => return Some(ways(svr, dac) * dac_fft * ways(fft, out)),
-        (0, fft_dac) => return Some(ways(svr, fft) * fft_dac * ways(dac, out)),
-        (_, _) => unreachable!(),
+        (dac_fft, 0) => Ok(ways(svr, dac) * dac_fft * ways(fft, out)),
+        (0, fft_dac) => Ok(ways(svr, fft) * fft_dac * ways(dac, out)),
+        (_, _) => Err("found paths both from dac to fft and from fft to dac".into()),
+    }
+}
+
+/// A layered graph of `size` devices. Edges only lead to the next layer, so it is acyclic, and the number of
+/// paths stays within `u64` like in puzzle inputs. `you`, `dac` and `fft` are placed in different layers,
+/// on a path from `svr` to `out`.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let size = size.clamp(5, 26 * 26 * 26 - 1);
+    let depth = (size / 4).clamp(4, 32);
+
+    let reserved = ["svr", "you", "dac", "fft", "out"];
+    let mut names = rng
+        .distinct_sorted(size + reserved.len(), 0..26 * 26 * 26)
+        .into_iter()
+        .map(|n| {
+            [n / 676, n / 26 % 26, n % 26]
+                .map(|c| char::from(b'a' + c as u8))
+                .iter()
+                .collect::<String>()
+        })
+        .filter(|name| !reserved.contains(&name.as_str()))
+        .take(size - 4)
+        .collect::<Vec<_>>()
+        .into_iter();
+
+    let mut special = [(depth / 2, "dac"), (3 * depth / 4, "fft")];
+    if rng.chance(0.5) {
+        (special[0].1, special[1].1) = (special[1].1, special[0].1);
+    }
+    let special = [
+        (0, "svr"),
+        ((depth / 4).max(1), "you"),
+        special[0],
+        special[1],
+    ];
+
+    // the remaining devices are spread evenly over the layers after `svr`.
+    let mut layers = vec![vec!["svr".to_string()]];
+    for layer in 1..=depth {
+        let width = (size - 1 - (layer - 1)).div_ceil(depth);
+        let name = special.iter().find(|(l, _)| *l == layer).map(|(_, n)| n);
+
+        let mut devices: Vec<String> = names
+            .by_ref()
+            .take(width - usize::from(name.is_some()))
+            .collect();
+        if let Some(name) = name {
+            let at = rng.below(devices.len() as u64 + 1) as usize;
+            devices.insert(at, name.to_string());
+        }
+        layers.push(devices);
+    }
+
+    let mut edges: Vec<Vec<Vec<String>>> = layers
+        .windows(2)
+        .map(|pair| {
+            let next = &pair[1];
+            pair[0]
+                .iter()
+                .map(|_| {
+                    let mut targets = vec![rng.pick(next).clone()];
+                    let other = rng.pick(next);
+                    if rng.chance(0.5) && !targets.contains(other) {
+                        targets.push(other.clone());
+                    }
+                    targets
+                })
+                .collect()
+        })
+        .collect();
+    edges.push(vec![vec!["out".to_string()]; layers[depth].len()]);
+
+    // a path through `svr`, `you`, `dac` and `fft`, so no answer is trivially 0.
+    let path: Vec<usize> = (0..=depth)
+        .map(|layer| match special.iter().find(|(l, _)| *l == layer) {
+            Some((_, name)) => layers[layer].iter().position(|d| d == name).unwrap(),
+            None => rng.below(layers[layer].len() as u64) as usize,
+        })
+        .collect();
+    for layer in 0..depth {
+        let target = layers[layer + 1][path[layer + 1]].clone();
+        let targets = &mut edges[layer][path[layer]];
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
     }
+
+    let mut lines: Vec<String> = layers
+        .iter()
+        .zip(&edges)
+        .flat_map(|(devices, targets)| {
+            devices
+                .iter()
+                .zip(targets)
+                .map(|(device, targets)| format!("{device}: {}", targets.join(" ")))
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+
+    lines.join("\n") + "\n"
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_generated_inputs() {
+        for seed in 0..10 {
+            let input = generate(200, &mut Rng::new(seed));
+            assert_eq!(input.lines().count(), 200);
+            assert!(part_one(&input).unwrap() > 0);
+            assert!(part_two(&input).unwrap() > 0);
+        }
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5));
+        assert_eq!(result, Ok(5));
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(
-            "svr: aaa bbb
-aaa: fft
-fft: ccc
-bbb: tty
-tty: ccc
-ccc: ddd eee
-ddd: hub
-hub: fff
-eee: dac
-dac: fff
-fff: ggg hhh
-ggg: out
-hhh: out
-",
-        );
-        assert_eq!(result, Some(2));
+    fn test_malformed_input() {
+        assert!(part_one("you: out\n").is_ok());
+        assert!(part_one("you out\n").is_err());
+        assert!(part_one("you: OUT\n").is_err());
+        assert!(part_two("svr: dac fftt\n").is_err());
     }
+
+    advent_of_code::example_tests!(11);
 }
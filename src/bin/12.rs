@@ -1,10 +1,90 @@
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, assume = [six_shapes_first, regions_decided_by_area]);
+
+/// Lines taken by the shapes at the start of the input: an index, three rows and a blank line per shape.
+const SHAPE_LINES: usize = 30;
+
+fn six_shapes_first(input: &str) -> Result<(), String> {
+    let lines: Vec<&str> = input.lines().take(SHAPE_LINES).collect();
+    for (i, shape) in lines.chunks(5).enumerate() {
+        let valid = shape.len() == 5
+            && shape[0] == format!("{i}:")
+            && shape[1..4]
+                .iter()
+                .all(|row| row.len() == 3 && row.bytes().all(|b| b == b'#' || b == b'.'))
+            && shape[4].is_empty();
+        if !valid {
+            return Err(format!(
+                "lines {} to {} are not shape {i} followed by a blank line.",
+                i * 5 + 1,
+                i * 5 + 5
+            ));
+        }
+    }
+    if lines.len() < SHAPE_LINES {
+        return Err(format!("expected {SHAPE_LINES} lines of shapes."));
+    }
+    Ok(())
+}
+
+/// The width, height and present counts of a region line, `None` if it is malformed.
+fn parse_region(region: &str) -> Option<(usize, usize, Vec<usize>)> {
+    let (size, counts) = region.split_once(": ")?;
+    let (width, height) = size.split_once('x')?;
+    let counts = counts
+        .split(' ')
+        .map(|n| n.parse().ok())
+        .collect::<Option<_>>()?;
+    Some((width.parse().ok()?, height.parse().ok()?, counts))
+}
+
+/// Each region either fits all of its presents as 3x3 squares, or is smaller than their cells, so packing the
+/// actual shapes never matters.
+fn regions_decided_by_area(input: &str) -> Result<(), String> {
+    let shapes: Vec<usize> = input
+        .lines()
+        .take(SHAPE_LINES)
+        .collect::<Vec<_>>()
+        .chunks(5)
+        .map(|shape| {
+            shape
+                .iter()
+                .skip(1)
+                .take(3)
+                .map(|row| row.matches('#').count())
+                .sum()
+        })
+        .collect();
+
+    for (i, region) in input.lines().skip(SHAPE_LINES).enumerate() {
+        let Some((width, height, counts)) = parse_region(region) else {
+            return Err(format!(
+                "region {} is not `WxH: counts`, got {region:?}.",
+                i + 1
+            ));
+        };
+
+        // saturating, so that huge numbers in a malformed input are reported rather than overflow.
+        let presents = counts.iter().fold(0_usize, |sum, n| sum.saturating_add(*n));
+        let cells = counts.iter().zip(&shapes).fold(0_usize, |sum, (n, cells)| {
+            sum.saturating_add(n.saturating_mul(*cells))
+        });
+        if (width / 3).saturating_mul(height / 3) < presents
+            && width.saturating_mul(height) >= cells
+        {
+            return Err(format!(
+                "region {} ({width}x{height}) cannot fit its presents as 3x3 squares, but has room for their cells.",
+                i + 1
+            ));
+        }
+    }
+    Ok(())
+}
 
 #[inline(never)]
 pub fn part_one(input: &str) -> Option<u64> {
     let count = input
         .lines()
-        .skip(30)
+        .skip(SHAPE_LINES)
         .filter(|region| {
             let (size, counts) = region.split_once(':').unwrap();
             let region_size: usize = size
@@ -35,7 +115,28 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(0));
+    }
+
+    #[test]
+    fn test_assumptions() {
+        let shapes = "0:\n###\n##.\n##.\n\n".to_string()
+            + "1:\n###\n##.\n.##\n\n"
+            + "2:\n.##\n###\n##.\n\n"
+            + "3:\n##.\n###\n##.\n\n"
+            + "4:\n###\n#..\n###\n\n"
+            + "5:\n###\n.#.\n###\n\n";
+        assert_eq!(six_shapes_first(&shapes), Ok(()));
+        assert!(six_shapes_first(&shapes[5..]).is_err());
+
+        let input = shapes.clone() + "6x6: 1 1 1 1 0 0\n4x4: 3 0 0 0 0 0\n";
+        assert_eq!(regions_decided_by_area(&input), Ok(()));
+        assert_eq!(
+            regions_decided_by_area(&(shapes.clone() + "6x5: 2 1 0 0 0 0\n")),
+            Err("region 1 (6x5) cannot fit its presents as 3x3 squares, but has room for their cells.".into())
+        );
+        assert!(regions_decided_by_area(&(shapes.clone() + "6x5 2 1\n")).is_err());
+        assert!(regions_decided_by_area(&shapes[..20]).is_ok());
     }
 
     #[test]
@@ -1,51 +1,155 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, check, config, diff, download, generate, inputs, leaderboard, read, report, scaffold,
+    scale, solve, status, time, verify,
+};
+use advent_of_code::template::config as template_config;
+use advent_of_code::template::{cli, render};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
     use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::cli::{self, Invocation, Shell};
+    use advent_of_code::template::commands::scaffold::Artifact;
+    use advent_of_code::template::commands::{diff, scale};
+    use advent_of_code::template::render::Format;
+    use std::path::PathBuf;
+    use std::{env, process};
+
+    /// Global flags that override settings of `aoc.toml`.
+    pub struct Overrides {
+        pub year: Option<u16>,
+        pub format: Option<Format>,
+    }
 
     pub enum AppArguments {
         Download {
             day: Day,
+            force: Vec<Artifact>,
+            dry_run: bool,
         },
         Read {
             day: Day,
+            part: Option<u8>,
         },
         Scaffold {
             day: Day,
             download: bool,
-            overwrite: bool,
+            force: Vec<Artifact>,
+            dry_run: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
-            release: bool,
+            release: Option<bool>,
             dhat: bool,
             submit: Option<u8>,
+            verbose: bool,
         },
         All {
-            release: bool,
+            release: Option<bool>,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
         },
+        Report {
+            out: String,
+        },
+        Verify {
+            day: Option<Day>,
+            inputs: Vec<&'static str>,
+            release: Option<bool>,
+            junit: Option<String>,
+        },
+        Status {
+            run_tests: bool,
+            readme: bool,
+        },
+        Config,
+        Seal,
+        Unseal {
+            overwrite: bool,
+        },
+        Check {
+            day: Day,
+            files: Vec<PathBuf>,
+        },
+        Diff {
+            day: Day,
+            runs: u64,
+            size: usize,
+            seed: Option<u64>,
+            mutate: bool,
+            release: Option<bool>,
+        },
+        Scale {
+            day: Day,
+            part: u8,
+            size: usize,
+            steps: u32,
+            seed: Option<u64>,
+        },
+        Gen {
+            day: Day,
+            size: usize,
+            seed: Option<u64>,
+            release: Option<bool>,
+        },
+        Leaderboard {
+            id: u64,
+            day: Option<Day>,
+        },
+        Completions {
+            shell: Shell,
+        },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    /// `--release` and `--debug` override the `release` setting, which is used if neither is passed.
+    fn parse_release(args: &mut pico_args::Arguments) -> Option<bool> {
+        let release = args.contains("--release");
+        let debug = args.contains("--debug");
+        match (release, debug) {
+            (true, _) => Some(true),
+            (false, true) => Some(false),
+            (false, false) => None,
+        }
+    }
+
+    pub fn parse() -> Result<(AppArguments, Overrides), Box<dyn std::error::Error>> {
+        let raw_args: Vec<String> = env::args().skip(1).collect();
+
+        // the command table catches typos and prints help before any values are parsed.
+        match cli::validate(&raw_args) {
+            Ok(Invocation::Run(_)) => {}
+            Ok(Invocation::Help(command)) => {
+                print!("{}", cli::help(command));
+                process::exit(0);
+            }
+            Err(cli::Error::NoCommand) => {
+                eprint!("{}", cli::help(None));
+                process::exit(1);
+            }
+            Err(err) => return Err(err.into()),
+        }
+
         let mut args = pico_args::Arguments::from_env();
 
+        let overrides = Overrides {
+            year: args.opt_value_from_str("--year")?,
+            format: args.opt_value_from_str("--format")?,
+        };
+
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+                release: parse_release(&mut args),
             },
             Some("time") => {
                 let all = args.contains("--all");
@@ -58,40 +162,119 @@ mod args {
                 }
             }
             Some("download") => AppArguments::Download {
+                force: args.values_from_str("--force")?,
+                dry_run: args.contains("--dry-run"),
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                part: args.opt_value_from_str("--part")?,
                 day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
                 download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+                force: args.values_from_str("--force")?,
+                dry_run: args.contains("--dry-run"),
+                template: args.opt_value_from_str("--template")?,
+                day: args.free_from_str()?,
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
+                release: parse_release(&mut args),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                verbose: args.contains("--verbose"),
             },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            Some("report") => AppArguments::Report {
+                out: args
+                    .opt_value_from_str("--out")?
+                    .unwrap_or_else(|| "target/report.html".into()),
+            },
+            Some("verify") => {
+                let only_inputs = args.contains("--inputs");
+                let only_examples = args.contains("--examples");
+
+                let inputs = match (only_inputs, only_examples) {
+                    (true, false) => vec!["input"],
+                    (false, true) => vec!["example"],
+                    _ => vec!["input", "example"],
+                };
+
+                AppArguments::Verify {
+                    inputs,
+                    release: parse_release(&mut args),
+                    junit: args.opt_value_from_str("--junit")?,
+                    day: args.opt_free_from_str()?,
+                }
             }
+            Some("status") => AppArguments::Status {
+                run_tests: !args.contains("--no-tests"),
+                readme: args.contains("--readme"),
+            },
+            Some("config") => match args.subcommand()?.as_deref() {
+                Some("show") => AppArguments::Config,
+                _ => unreachable!("validated by the command table"),
+            },
+            Some("inputs") => match args.subcommand()?.as_deref() {
+                Some("seal") => AppArguments::Seal,
+                Some("unseal") => AppArguments::Unseal {
+                    overwrite: args.contains("--overwrite"),
+                },
+                _ => unreachable!("validated by the command table"),
+            },
+            Some("check") => AppArguments::Check {
+                files: args.values_from_str("--file")?,
+                day: args.free_from_str()?,
+            },
+            Some("diff") => AppArguments::Diff {
+                runs: args
+                    .opt_value_from_str("--runs")?
+                    .unwrap_or(diff::DEFAULT_RUNS),
+                size: args
+                    .opt_value_from_str("--size")?
+                    .unwrap_or(diff::DEFAULT_SIZE),
+                seed: args.opt_value_from_str("--seed")?,
+                mutate: args.contains("--mutate"),
+                release: parse_release(&mut args),
+                day: args.free_from_str()?,
+            },
+            Some("scale") => AppArguments::Scale {
+                part: args.value_from_str("--part")?,
+                size: args
+                    .opt_value_from_str("--size")?
+                    .unwrap_or(scale::DEFAULT_SIZE),
+                steps: args
+                    .opt_value_from_str("--steps")?
+                    .unwrap_or(scale::DEFAULT_STEPS),
+                seed: args.opt_value_from_str("--seed")?,
+                day: args.free_from_str()?,
+            },
+            Some("gen") => AppArguments::Gen {
+                size: args.value_from_str("--size")?,
+                seed: args.opt_value_from_str("--seed")?,
+                release: parse_release(&mut args),
+                day: args.free_from_str()?,
+            },
+            Some("leaderboard") => AppArguments::Leaderboard {
+                day: args.opt_value_from_str("--day")?,
+                id: args.free_from_str()?,
+            },
+            Some("completions") => AppArguments::Completions {
+                shell: args.free_from_str()?,
+            },
+            #[cfg(feature = "today")]
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
+            Some(x) => unreachable!("unknown command `{x}` passed validation"),
+            None => unreachable!("missing command passed validation"),
         };
 
         let remaining = args.finish();
         if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+            return Err(format!("unexpected argument(s): {remaining:?}.").into());
         }
 
-        Ok(app_args)
+        Ok((app_args, overrides))
     }
 }
 
@@ -101,44 +284,105 @@ fn main() {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+        Ok((args, overrides)) => {
+            let mut config = template_config::load();
+            if let Some(year) = overrides.year {
+                config.year = Some(year);
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+            if let Some(format) = overrides.format {
+                render::set_format(format);
             }
-        },
+            let default_release = config.release;
+            template_config::init(config);
+
+            run(args, default_release);
+        }
     };
 }
+
+fn run(args: AppArguments, default_release: bool) {
+    match args {
+        AppArguments::All { release } => all::handle(release.unwrap_or(default_release)),
+        AppArguments::Time { day, all, store } => time::handle(day, all, store),
+        AppArguments::Download {
+            day,
+            force,
+            dry_run,
+        } => download::handle(day, &force, dry_run),
+        AppArguments::Read { day, part } => read::handle(day, part),
+        AppArguments::Scaffold {
+            day,
+            download,
+            force,
+            dry_run,
+            template,
+        } => {
+            scaffold::handle(day, &force, dry_run, template.as_deref());
+            if download {
+                download::handle(day, &force, dry_run);
+            }
+        }
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            verbose,
+        } => solve::handle(
+            day,
+            release.unwrap_or(default_release),
+            dhat,
+            submit,
+            verbose,
+        ),
+        AppArguments::Report { out } => report::handle(&out),
+        AppArguments::Verify {
+            day,
+            inputs,
+            release,
+            junit,
+        } => verify::handle(
+            day,
+            &inputs,
+            release.unwrap_or(default_release),
+            junit.as_deref(),
+        ),
+        AppArguments::Status { run_tests, readme } => status::handle(run_tests, readme),
+        AppArguments::Config => config::handle(),
+        AppArguments::Seal => inputs::handle_seal(),
+        AppArguments::Unseal { overwrite } => inputs::handle_unseal(overwrite),
+        AppArguments::Check { day, files } => check::handle(day, &files),
+        AppArguments::Diff {
+            day,
+            runs,
+            size,
+            seed,
+            mutate,
+            release,
+        } => diff::handle(
+            day,
+            runs,
+            size,
+            seed,
+            mutate,
+            release.unwrap_or(default_release),
+        ),
+        AppArguments::Scale {
+            day,
+            part,
+            size,
+            steps,
+            seed,
+        } => scale::handle(day, part, size, steps, seed),
+        AppArguments::Gen {
+            day,
+            size,
+            seed,
+            release,
+        } => generate::handle(day, size, seed, release.unwrap_or(default_release)),
+        AppArguments::Leaderboard { id, day } => leaderboard::handle(id, day),
+        AppArguments::Completions { shell } => print!("{}", cli::completions(shell)),
+        #[cfg(feature = "today")]
+        AppArguments::Today { wait } => today::handle(wait),
+    }
+}
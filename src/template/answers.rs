@@ -0,0 +1,177 @@
+/// Known answers of a day, read from `data/answers/NN.toml`.
+///
+/// Answers are grouped by input, e.g.:
+/// ```toml
+/// [input]
+/// part_1 = 1234
+/// part_2 = "ABCDEFGH"
+///
+/// [example]
+/// part_1 = 3
+/// ```
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::{fs, io};
+
+use crate::template::config::{self, Error, Value, parse_toml};
+use crate::template::{Day, ocr};
+
+/// Inputs a solution can be verified against.
+pub static INPUT_KINDS: [&str; 2] = ["input", "example"];
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    /// Answers keyed by `input.part_N`.
+    entries: HashMap<String, String>,
+}
+
+impl Answers {
+    fn path(day: Day) -> String {
+        format!("{}/{day}.toml", config::get().data_dir("answers"))
+    }
+
+    /// Reads the answers of a day. If not present, returns no answers.
+    pub fn read(day: Day) -> Result<Self, Error> {
+        match fs::read_to_string(Self::path(day)) {
+            Ok(s) => Self::try_from(s.as_str()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::IO(e)),
+        }
+    }
+
+    /// Returns the expected answer of a part for an input kind, e.g. `example`.
+    pub fn get(&self, input: &str, part: u8) -> Option<&str> {
+        self.entries
+            .get(&format!("{input}.part_{part}"))
+            .map(String::as_str)
+    }
+
+    /// Stores an answer of a day, e.g. after it was accepted. Comments in the file are not preserved.
+    pub fn record(day: Day, input: &str, part: u8, answer: &str) -> Result<(), Error> {
+        let mut answers = Self::read(day)?;
+        answers
+            .entries
+            .insert(format!("{input}.part_{part}"), answer.to_string());
+
+        let path = Self::path(day);
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent).map_err(Error::IO)?;
+        }
+        fs::write(path, answers.to_string()).map_err(Error::IO)
+    }
+
+    /// Whether `answer` matches the expected answer. Letter art is compared as the text it spells.
+    pub fn matches(expected: &str, answer: &str) -> bool {
+        if answer.contains('\n') {
+            ocr::recognize(answer).is_some_and(|text| text == expected)
+        } else {
+            answer == expected
+        }
+    }
+}
+
+impl TryFrom<&str> for Answers {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut entries = HashMap::new();
+
+        for (key, value) in parse_toml(value)? {
+            let valid = key.split_once('.').is_some_and(|(input, part)| {
+                INPUT_KINDS.contains(&input) && (part == "part_1" || part == "part_2")
+            });
+
+            if !valid {
+                return Err(Error::Invalid(format!("unknown answer `{key}`.")));
+            }
+
+            let answer = match value {
+                Value::String(s) => s,
+                Value::Integer(i) => i.to_string(),
+                Value::Bool(_) => {
+                    return Err(Error::Invalid(format!(
+                        "`{key}` must be a string or an integer."
+                    )));
+                }
+            };
+
+            entries.insert(key, answer);
+        }
+
+        Ok(Self { entries })
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+
+        for input in INPUT_KINDS {
+            let parts: Vec<(u8, &str)> = (1..=2)
+                .filter_map(|part| Some((part, self.get(input, part)?)))
+                .collect();
+
+            if parts.is_empty() {
+                continue;
+            }
+
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+
+            writeln!(f, "[{input}]")?;
+            for (part, answer) in parts {
+                // answers that read back as the same integer are written without quotes.
+                if answer.parse::<i64>().is_ok_and(|i| i.to_string() == answer) {
+                    writeln!(f, "part_{part} = {answer}")?;
+                } else {
+                    let escaped = answer.replace('\\', "\\\\").replace('"', "\\\"");
+                    writeln!(f, "part_{part} = \"{escaped}\"")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+
+    #[test]
+    fn handles_answers() {
+        let answers =
+            Answers::try_from("[input]\npart_1 = 1234\npart_2 = \"AB\"\n\n[example]\npart_1 = 3")
+                .unwrap();
+        assert_eq!(answers.get("input", 1), Some("1234"));
+        assert_eq!(answers.get("input", 2), Some("AB"));
+        assert_eq!(answers.get("example", 1), Some("3"));
+        assert_eq!(answers.get("example", 2), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_unknown_answers() {
+        Answers::try_from("[input]\npart_3 = 1").unwrap();
+    }
+
+    #[test]
+    fn roundtrips_display() {
+        let source = "[input]\npart_1 = 1234\npart_2 = \"007\"\n\n[example]\npart_2 = \"AB\"\n";
+        let answers = Answers::try_from(source).unwrap();
+        assert_eq!(answers.to_string(), source);
+    }
+
+    #[test]
+    fn matches_letter_art() {
+        let art = ".##.\n#..#\n#..#\n####\n#..#\n#..#";
+        assert!(Answers::matches("A", art));
+        assert!(!Answers::matches("B", art));
+        assert!(Answers::matches("42", "42"));
+    }
+}
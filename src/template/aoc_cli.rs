@@ -1,16 +1,22 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
+    env,
     fmt::Display,
+    fs,
+    io::Write,
+    path::PathBuf,
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{Day, config};
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    /// No session cookie was found for a request that aoc-cli does not support.
+    MissingSession,
 }
 
 impl Display for AocCommandError {
@@ -21,6 +27,10 @@ impl Display for AocCommandError {
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::MissingSession => write!(
+                f,
+                "no session cookie found. Configure `session.file` or `session.env` in aoc.toml."
+            ),
         }
     }
 }
@@ -46,59 +56,153 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
         day,
     );
 
-    call_aoc_cli(&args)
+    call_aoc_cli(&args, Stdio::inherit())
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
+/// The files written by [`download`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Download {
+    Both,
+    InputOnly,
+    PuzzleOnly,
+}
+
+/// Downloads the input and/or puzzle description. Existing files are only replaced with `overwrite`,
+/// aoc-cli fails otherwise.
+pub fn download(day: Day, files: Download, overwrite: bool) -> Result<Output, AocCommandError> {
     let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
 
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
+    let mut args = vec![
+        "--input-file".into(),
+        input_path.to_string(),
+        "--puzzle-file".into(),
+        puzzle_path.to_string(),
+    ];
+    if overwrite {
+        args.push("--overwrite".into());
+    }
+    match files {
+        Download::Both => {}
+        Download::InputOnly => args.push("--input-only".into()),
+        Download::PuzzleOnly => args.push("--puzzle-only".into()),
+    }
 
-    let output = call_aoc_cli(&args)?;
+    let args = build_args("download", &args, day);
+
+    let output = call_aoc_cli(&args, Stdio::inherit())?;
     println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    if files != Download::PuzzleOnly {
+        println!("🎄 Successfully wrote input to \"{input_path}\".");
+    }
+    if files != Download::InputOnly {
+        println!("🎄 Successfully wrote puzzle to \"{puzzle_path}\".");
+    }
     Ok(output)
 }
 
+/// Submits an answer. The output of aoc-cli is captured, see [`is_accepted`].
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    let result = call_aoc_cli(&args, Stdio::piped());
+    if let Ok(output) | Err(AocCommandError::BadExitStatus(output)) = &result {
+        print!("{}", String::from_utf8_lossy(&output.stdout));
+    }
+    result
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+/// Whether the output of [`submit`] reports the answer as correct.
+pub fn is_accepted(output: &Output) -> bool {
+    String::from_utf8_lossy(&output.stdout).contains("That's the right answer")
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+/// Identifies requests to the site, as asked for by its automation guidelines: the `repository` of
+/// `Cargo.toml` if set, otherwise the package name and version.
+fn user_agent() -> String {
+    match env!("CARGO_PKG_REPOSITORY") {
+        "" => concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")).into(),
+        repository => repository.into(),
+    }
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+/// Fetches the JSON of a private leaderboard. aoc-cli only prints leaderboards as tables, so this
+/// calls `curl` with the same session cookie that is passed to aoc-cli.
+pub fn leaderboard(year: u16, id: u64) -> Result<Output, AocCommandError> {
+    let session = session().ok_or(AocCommandError::MissingSession)?;
+    let url = format!("https://adventofcode.com/{year}/leaderboard/private/view/{id}.json");
+
+    // the cookie is passed on stdin to keep it out of the process list.
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--header", "@-"])
+        .args(["--user-agent", &user_agent(), &url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "Cookie: session={session}")
+            .map_err(|_| AocCommandError::CommandNotCallable)?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
     }
 }
 
+/// The session cookie of all requests, looked up like aoc-cli does: the configured file or variable,
+/// `ADVENT_OF_CODE_SESSION`, then `~/.adventofcode.session` and the config directory.
+fn session() -> Option<String> {
+    let config = config::get();
+    let read = |path: PathBuf| fs::read_to_string(path).ok();
+
+    let session = match (&config.session.file_path(), &config.session.env) {
+        (Some(file), _) => read(file.into()),
+        (None, Some(var)) => env::var(var).ok().or_else(|| {
+            eprintln!("Warning: session variable `{var}` is not set.");
+            None
+        }),
+        (None, None) => env::var("ADVENT_OF_CODE_SESSION").ok().or_else(|| {
+            let home = env::var("HOME").ok().map(PathBuf::from);
+            let config_dir = env::var("XDG_CONFIG_HOME")
+                .ok()
+                .map(PathBuf::from)
+                .or_else(|| home.as_ref().map(|h| h.join(".config")));
+
+            home.map(|h| h.join(".adventofcode.session"))
+                .and_then(read)
+                .or_else(|| config_dir.and_then(|d| read(d.join("adventofcode.session"))))
+        }),
+    }?;
+
+    let session = session.trim().to_string();
+    (!session.is_empty()).then_some(session)
+}
+
+fn get_input_path(day: Day) -> String {
+    format!("{}/{day}.txt", config::get().data_dir("inputs"))
+}
+
+fn get_puzzle_path(day: Day) -> String {
+    format!("{}/{day}.md", config::get().data_dir("puzzles"))
+}
+
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+    let config = config::get();
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
+    if let Some(year) = config.year {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
@@ -108,11 +212,18 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+fn call_aoc_cli(args: &[String], stdout: Stdio) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
+    let mut cmd = Command::new("aoc");
+
+    // aoc-cli reads the session cookie from this variable, which keeps it the same as for `leaderboard`.
+    if let Some(session) = session() {
+        cmd.env("ADVENT_OF_CODE_SESSION", session);
+    }
+
+    let output = cmd
         .args(args)
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
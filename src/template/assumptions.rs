@@ -0,0 +1,347 @@
+/// Assumptions of solutions about the shape of their input, declared as named checks with `solution!`:
+/// ```ignore
+/// advent_of_code::solution!(7, assume = [every_other_row_empty, no_splitter_on_edge]);
+///
+/// fn no_splitter_on_edge(input: &str) -> Result<(), String> {
+///     // `Err` describes the violation, e.g. "row 5 has a splitter on its edge."
+/// }
+/// ```
+/// Checks may take a second argument `ctx: &Context`, e.g. to only apply to real inputs. They must return `Err`
+/// instead of panicking on malformed inputs: the release profile aborts on panics, so a panicking check is only
+/// reported as violated in debug builds and by `check` and `diff`.
+///
+/// Debug builds validate every input before running the parts. Release builds only validate inputs that have
+/// not passed before, so that switching to another input is noticed. `check <day>` validates all inputs of a day.
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::{env, process};
+
+use crate::template::context::Context;
+use crate::template::examples::Example;
+use crate::template::runner::panic_message;
+use crate::template::{Day, config, input_path, try_read_file};
+
+/// Assumption checks, which take the input and optionally the [`Context`] of the run.
+/// `M` only tells the two signatures apart.
+pub trait Check<M> {
+    fn check(&self, input: &str, ctx: &Context) -> Result<(), String>;
+}
+
+impl<F: Fn(&str) -> Result<(), String>> Check<()> for F {
+    fn check(&self, input: &str, _: &Context) -> Result<(), String> {
+        self(input)
+    }
+}
+
+impl<F: Fn(&str, &Context) -> Result<(), String>> Check<Context> for F {
+    fn check(&self, input: &str, ctx: &Context) -> Result<(), String> {
+        self(input, ctx)
+    }
+}
+
+type CheckFn = dyn Fn(&str, &Context) -> Result<(), String>;
+
+/// A named check, built by `solution!`.
+pub struct Assumption {
+    pub name: &'static str,
+    check: Box<CheckFn>,
+}
+
+impl Assumption {
+    pub fn new<M>(name: &'static str, check: impl Check<M> + 'static) -> Self {
+        Self {
+            name,
+            check: Box::new(move |input, ctx| check.check(input, ctx)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub name: &'static str,
+    pub message: String,
+}
+
+/// Runs all checks against an input. Checks that panic are violated, unless the profile aborts on panics.
+pub fn violations(assumptions: &[Assumption], input: &str, ctx: &Context) -> Vec<Violation> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let violations = assumptions
+        .iter()
+        .filter_map(|assumption| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| (assumption.check)(input, ctx)))
+                .unwrap_or_else(|payload| {
+                    Err(format!(
+                        "the check panicked: {}",
+                        panic_message(payload.as_ref())
+                    ))
+                });
+            result.err().map(|message| Violation {
+                name: assumption.name,
+                message,
+            })
+        })
+        .collect();
+
+    panic::set_hook(hook);
+    violations
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Inputs that passed the assumptions of a day, by their hash.
+fn cache_path(day: Day) -> PathBuf {
+    Path::new(&config::get().data_dir("assumptions")).join(format!("{day}.txt"))
+}
+
+/// Hashes the input together with the names of the checks, so that new assumptions are validated as well.
+fn fingerprint(assumptions: &[Assumption], input: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    for assumption in assumptions {
+        assumption.name.hash(&mut hasher);
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// Validates the input of a run, see the module docs. Exits if it violates an assumption.
+pub fn validate(day: Day, path: &Path, assumptions: &[Assumption], input: &str, ctx: &Context) {
+    if assumptions.is_empty() {
+        return;
+    }
+
+    let cache = cache_path(day);
+    let fingerprint = fingerprint(assumptions, input);
+    let known =
+        fs::read_to_string(&cache).is_ok_and(|known| known.lines().any(|line| line == fingerprint));
+
+    if !cfg!(debug_assertions) && known {
+        return;
+    }
+
+    let violations = violations(assumptions, input, ctx);
+    if violations.is_empty() {
+        if !known {
+            // the cache only saves time, failing to write it is not worth a warning.
+            let _ = fs::create_dir_all(cache.parent().unwrap()).and_then(|()| {
+                let mut known = fs::read_to_string(&cache).unwrap_or_default();
+                known.push_str(&fingerprint);
+                known.push('\n');
+                fs::write(&cache, known)
+            });
+        }
+        return;
+    }
+
+    eprintln!(
+        "Error: \"{}\" violates {} assumption(s) of day {day}:",
+        path.display(),
+        violations.len()
+    );
+    for violation in &violations {
+        eprintln!("  ✗ {}: {}", violation.name, violation.message);
+    }
+    eprintln!(
+        "The solution relies on them and could give a wrong answer or crash. Run `check {day}` to check all inputs."
+    );
+    process::exit(1);
+}
+
+/// The arguments `check` passes to a solution: `--assumptions [--file <path>]..`.
+pub fn request() -> Option<Vec<PathBuf>> {
+    let args: Vec<String> = env::args().collect();
+    if !args.iter().any(|arg| arg == "--assumptions") {
+        return None;
+    }
+
+    Some(
+        args.windows(2)
+            .filter(|pair| pair[0] == "--file")
+            .map(|pair| PathBuf::from(&pair[1]))
+            .collect(),
+    )
+}
+
+/// The input and the examples of a day, and `files`, with the context to check them in.
+fn inputs(
+    day: Day,
+    files: &[PathBuf],
+    context: &dyn Fn(&str) -> Context,
+) -> Vec<(PathBuf, String, Context)> {
+    let mut inputs = vec![];
+
+    for folder in ["inputs", "examples"] {
+        if let Ok(input) = try_read_file(folder, day)
+            && !input.trim().is_empty()
+        {
+            inputs.push((input_path(folder, day), input, context(folder)));
+        }
+    }
+
+    let dir = Path::new(&config::get().data_dir("examples")).join(day.to_string());
+    let mut examples: Vec<PathBuf> = fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    examples.sort();
+
+    for path in examples {
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        let Ok(example) = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| Example::parse(&name, &text).map_err(|e| e.to_string()))
+        else {
+            eprintln!(
+                "Warning: skipped \"{}\", it is not a valid example file.",
+                path.display()
+            );
+            continue;
+        };
+        let mut ctx = context("examples");
+        ctx.params.merge(example.params());
+        inputs.push((path, example.input, ctx));
+    }
+
+    for path in files {
+        match fs::read_to_string(path) {
+            Ok(input) => inputs.push((path.clone(), input, context("inputs"))),
+            Err(e) => {
+                eprintln!("Error: could not read \"{}\": {e}", path.display());
+                process::exit(1);
+            }
+        }
+    }
+
+    inputs
+}
+
+/// Validates the input and the examples of a day, and `files`, printing which assumptions each of them violates,
+/// and exits. Called by `solution!` instead of running the parts.
+pub fn run(
+    day: Day,
+    assumptions: &[Assumption],
+    files: &[PathBuf],
+    context: &dyn Fn(&str) -> Context,
+) -> ! {
+    if assumptions.is_empty() {
+        eprintln!(
+            "Day {day} declares no assumptions. Declare them with `solution!({}, assume = [check])`.",
+            day.into_inner()
+        );
+        process::exit(1);
+    }
+
+    let inputs = inputs(day, files, context);
+    if inputs.is_empty() {
+        eprintln!("Day {day} has no input or example to check.");
+        process::exit(1);
+    }
+
+    let mut failed = 0;
+    for (path, input, ctx) in &inputs {
+        let violations = violations(assumptions, input, ctx);
+        if violations.is_empty() {
+            println!(
+                "✓ {}: {} assumption(s) hold.",
+                path.display(),
+                assumptions.len()
+            );
+            continue;
+        }
+
+        failed += 1;
+        println!(
+            "✗ {}: {} of {} assumption(s) violated.",
+            path.display(),
+            violations.len(),
+            assumptions.len()
+        );
+        for assumption in assumptions {
+            match violations.iter().find(|v| v.name == assumption.name) {
+                Some(violation) => println!("    ✗ {}: {}", violation.name, violation.message),
+                None => println!("    ✓ {}", assumption.name),
+            }
+        }
+    }
+
+    process::exit(if failed > 0 { 1 } else { 0 });
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Assumption, Violation, fingerprint, violations};
+    use crate::template::context::{Context, InputKind};
+    use crate::template::params::Params;
+
+    fn at_most_three_lines(input: &str) -> Result<(), String> {
+        match input.lines().count() {
+            n if n <= 3 => Ok(()),
+            n => Err(format!("found {n} lines.")),
+        }
+    }
+
+    fn only_numbers(input: &str) -> Result<(), String> {
+        for line in input.lines() {
+            line.parse::<u64>().unwrap();
+        }
+        Ok(())
+    }
+
+    fn real_inputs_are_long(input: &str, ctx: &Context) -> Result<(), String> {
+        if ctx.kind == InputKind::Real && input.lines().count() < 2 {
+            return Err("real inputs have at least 2 lines.".into());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn reports_violations() {
+        let assumptions = [
+            Assumption::new("at_most_three_lines", at_most_three_lines),
+            Assumption::new("only_numbers", only_numbers),
+            Assumption::new("real_inputs_are_long", real_inputs_are_long),
+        ];
+        let real = Context::new(InputKind::Real, Params::default());
+        let example = Context::new(InputKind::Example, Params::default());
+
+        assert_eq!(violations(&assumptions, "1\n2\n", &real), vec![]);
+        assert_eq!(violations(&assumptions, "1\n", &example), vec![]);
+
+        let found = violations(&assumptions, "1\n2\nx\n4\n", &real);
+        assert_eq!(
+            found[0],
+            Violation {
+                name: "at_most_three_lines",
+                message: "found 4 lines.".into()
+            }
+        );
+        assert_eq!(found[1].name, "only_numbers");
+        assert!(found[1].message.starts_with("the check panicked: "));
+        assert_eq!(found.len(), 2);
+
+        assert_eq!(
+            violations(&assumptions, "1\n", &real)[0].name,
+            "real_inputs_are_long"
+        );
+    }
+
+    #[test]
+    fn fingerprints_inputs_and_assumptions() {
+        let one = [Assumption::new("only_numbers", only_numbers)];
+        let two = [
+            Assumption::new("only_numbers", only_numbers),
+            Assumption::new("at_most_three_lines", at_most_three_lines),
+        ];
+        assert_eq!(fingerprint(&one, "1\n"), fingerprint(&one, "1\n"));
+        assert_ne!(fingerprint(&one, "1\n"), fingerprint(&one, "2\n"));
+        assert_ne!(fingerprint(&one, "1\n"), fingerprint(&two, "1\n"));
+    }
+}
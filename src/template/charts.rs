@@ -0,0 +1,300 @@
+/// Renders benchmark timings as standalone SVG charts, without any scripts or external resources.
+use std::fmt::Write;
+use std::time::Duration;
+
+pub static PART_1_COLOR: &str = "#4c78a8";
+pub static PART_2_COLOR: &str = "#f58518";
+
+const FONT: &str =
+    "font-family=\"ui-monospace, SFMono-Regular, Menlo, monospace\" font-size=\"12\"";
+const LABEL_WIDTH: f64 = 90.0;
+const VALUE_WIDTH: f64 = 80.0;
+const ROW_HEIGHT: f64 = 18.0;
+const AXIS_HEIGHT: f64 = 20.0;
+
+/// A single bar of a chart.
+pub struct Bar {
+    pub label: String,
+    /// The duration in nanoseconds.
+    pub nanos: f64,
+    pub color: &'static str,
+}
+
+/// Formats nanoseconds like the runner does, e.g. `1.2ms`.
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.max(0.0) as u64);
+    format!("{duration:.1?}")
+}
+
+/// Formats a power of ten for axis labels, e.g. `10µs`.
+fn format_decade(exp: i32) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(10_f64.powi(exp) as u64);
+    format!("{duration:?}")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The decades spanned by the values, e.g. `(3, 7)` for values between 1µs and 10ms.
+fn decades(values: impl Iterator<Item = f64>) -> (i32, i32) {
+    let (min, max) = values
+        .filter(|v| *v > 0.0)
+        .fold((f64::MAX, f64::MIN), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+
+    if min > max {
+        return (0, 1);
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    let (low, high) = (min.log10().floor() as i32, max.log10().ceil() as i32);
+    (low, high.max(low + 1))
+}
+
+/// Horizontal bars on a logarithmic scale, so that microseconds and seconds fit into one chart.
+/// Adds a line with `footer` below the bars, e.g. the total.
+pub fn log_bar_chart(bars: &[Bar], width: f64, footer: Option<&str>) -> String {
+    let (low, high) = decades(bars.iter().map(|b| b.nanos));
+    let plot_width = width - LABEL_WIDTH - VALUE_WIDTH;
+    let x = |nanos: f64| {
+        if nanos <= 0.0 {
+            return 0.0;
+        }
+        (nanos.log10() - f64::from(low)) / f64::from(high - low) * plot_width
+    };
+
+    let footer_height = if footer.is_some() {
+        ROW_HEIGHT + 6.0
+    } else {
+        0.0
+    };
+    #[allow(clippy::cast_precision_loss)]
+    let height = AXIS_HEIGHT + bars.len() as f64 * ROW_HEIGHT + footer_height + 4.0;
+
+    let mut out = svg_header(width, height);
+
+    // one gridline per decade.
+    for exp in low..=high {
+        let gx = LABEL_WIDTH + x(10_f64.powi(exp));
+        let _ = writeln!(
+            out,
+            "  <line x1=\"{gx:.1}\" y1=\"{AXIS_HEIGHT}\" x2=\"{gx:.1}\" y2=\"{:.1}\" stroke=\"#ccc\" stroke-width=\"1\"/>",
+            height - footer_height
+        );
+        let _ = writeln!(
+            out,
+            "  <text x=\"{gx:.1}\" y=\"14\" text-anchor=\"middle\" fill=\"#888\">{}</text>",
+            format_decade(exp)
+        );
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    for (i, bar) in bars.iter().enumerate() {
+        let y = AXIS_HEIGHT + i as f64 * ROW_HEIGHT;
+        let w = x(bar.nanos).max(1.0);
+        let _ = writeln!(
+            out,
+            "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\" fill=\"#666\">{}</text>",
+            LABEL_WIDTH - 6.0,
+            y + 13.0,
+            escape(&bar.label)
+        );
+        let _ = writeln!(
+            out,
+            "  <rect x=\"{LABEL_WIDTH}\" y=\"{:.1}\" width=\"{w:.1}\" height=\"{:.1}\" fill=\"{}\"><title>{}: {}</title></rect>",
+            y + 2.0,
+            ROW_HEIGHT - 4.0,
+            bar.color,
+            escape(&bar.label),
+            format_nanos(bar.nanos)
+        );
+        let _ = writeln!(
+            out,
+            "  <text x=\"{:.1}\" y=\"{:.1}\" fill=\"#666\">{}</text>",
+            LABEL_WIDTH + w + 4.0,
+            y + 13.0,
+            format_nanos(bar.nanos)
+        );
+    }
+
+    if let Some(footer) = footer {
+        let _ = writeln!(
+            out,
+            "  <text x=\"{LABEL_WIDTH}\" y=\"{:.1}\" font-weight=\"bold\" fill=\"#666\">{}</text>",
+            height - 8.0,
+            escape(footer)
+        );
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Vertical bars on a linear scale, e.g. the two parts of a day.
+pub fn bar_chart(bars: &[Bar], width: f64, height: f64) -> String {
+    let max = bars.iter().map(|b| b.nanos).fold(0.0, f64::max);
+    let plot_height = height - 2.0 * ROW_HEIGHT;
+    #[allow(clippy::cast_precision_loss)]
+    let slot = width / bars.len().max(1) as f64;
+
+    let mut out = svg_header(width, height);
+
+    #[allow(clippy::cast_precision_loss)]
+    for (i, bar) in bars.iter().enumerate() {
+        let h = if max > 0.0 {
+            (bar.nanos / max * plot_height).max(1.0)
+        } else {
+            0.0
+        };
+        let x = i as f64 * slot;
+        let y = ROW_HEIGHT + plot_height - h;
+        let _ = writeln!(
+            out,
+            "  <rect x=\"{:.1}\" y=\"{y:.1}\" width=\"{:.1}\" height=\"{h:.1}\" fill=\"{}\"><title>{}: {}</title></rect>",
+            x + slot * 0.2,
+            slot * 0.6,
+            bar.color,
+            escape(&bar.label),
+            format_nanos(bar.nanos)
+        );
+        let _ = writeln!(
+            out,
+            "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" fill=\"#666\">{}</text>",
+            x + slot / 2.0,
+            y - 4.0,
+            format_nanos(bar.nanos)
+        );
+        let _ = writeln!(
+            out,
+            "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" fill=\"#666\">{}</text>",
+            x + slot / 2.0,
+            height - 4.0,
+            escape(&bar.label)
+        );
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+/// A line over a series of labelled points on a logarithmic scale, e.g. a part's timing across commits.
+pub fn line_chart(points: &[(String, f64)], width: f64, height: f64, color: &str) -> String {
+    let (low, high) = decades(points.iter().map(|p| p.1));
+    let plot_height = height - AXIS_HEIGHT;
+    let plot_width = width - LABEL_WIDTH;
+    let y = |nanos: f64| {
+        let v = if nanos > 0.0 {
+            nanos.log10()
+        } else {
+            f64::from(low)
+        };
+        AXIS_HEIGHT / 2.0 + plot_height - (v - f64::from(low)) / f64::from(high - low) * plot_height
+    };
+    #[allow(clippy::cast_precision_loss)]
+    let x = |i: usize| LABEL_WIDTH + plot_width * (i as f64 + 0.5) / points.len().max(1) as f64;
+
+    let mut out = svg_header(width, height);
+
+    for exp in [low, high] {
+        let gy = y(10_f64.powi(exp));
+        let _ = writeln!(
+            out,
+            "  <line x1=\"{LABEL_WIDTH}\" y1=\"{gy:.1}\" x2=\"{width}\" y2=\"{gy:.1}\" stroke=\"#ccc\" stroke-width=\"1\"/>"
+        );
+        let _ = writeln!(
+            out,
+            "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\" fill=\"#888\">{}</text>",
+            LABEL_WIDTH - 6.0,
+            gy + 4.0,
+            format_decade(exp)
+        );
+    }
+
+    let path: Vec<String> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| format!("{:.1},{:.1}", x(i), y(p.1)))
+        .collect();
+
+    let _ = writeln!(
+        out,
+        "  <polyline points=\"{}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"2\"/>",
+        path.join(" ")
+    );
+
+    for (i, (label, nanos)) in points.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{color}\"><title>{}: {}</title></circle>",
+            x(i),
+            y(*nanos),
+            escape(label),
+            format_nanos(*nanos)
+        );
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+fn svg_header(width: f64, height: f64) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" {FONT}>\n"
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        Bar, PART_1_COLOR, PART_2_COLOR, decades, format_decade, format_nanos, log_bar_chart,
+    };
+
+    #[test]
+    fn formats_nanos() {
+        assert_eq!(format_nanos(1_500_000.0), "1.5ms");
+        assert_eq!(format_nanos(900.0), "900.0ns");
+        assert_eq!(format_decade(4), "10µs");
+    }
+
+    #[test]
+    fn computes_decades() {
+        assert_eq!(decades([1_500.0, 2_000_000.0].into_iter()), (3, 7));
+        assert_eq!(decades([1_000.0].into_iter()), (3, 4));
+        assert_eq!(decades(std::iter::empty()), (0, 1));
+    }
+
+    #[test]
+    fn renders_log_bars() {
+        let svg = log_bar_chart(
+            &[
+                Bar {
+                    label: "Day 01 · 1".into(),
+                    nanos: 1_000.0,
+                    color: PART_1_COLOR,
+                },
+                Bar {
+                    label: "Day 01 · 2".into(),
+                    nanos: 1_000_000.0,
+                    color: PART_2_COLOR,
+                },
+            ],
+            600.0,
+            Some("Total: 1.00ms"),
+        );
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("<title>Day 01 · 2: 1.0ms</title>"));
+        assert!(svg.contains("Total: 1.00ms"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect").count(), 2);
+    }
+}
@@ -0,0 +1,1111 @@
+/// Declarative description of the template CLI, used to validate arguments, print help and
+/// generate shell completions.
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+
+pub static BIN_NAME: &str = "advent_of_code";
+
+/// A subcommand of the CLI.
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub args: &'static [ArgSpec],
+    pub flags: &'static [FlagSpec],
+    /// Name of the cargo alias that runs this command, if there is one.
+    pub alias: Option<&'static str>,
+}
+
+/// A positional argument of a command.
+pub struct ArgSpec {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub required: bool,
+    /// Accepted values. Any value is accepted if empty.
+    pub values: &'static [&'static str],
+}
+
+/// A `--flag` of a command, optionally followed by a value.
+pub struct FlagSpec {
+    pub name: &'static str,
+    pub help: &'static str,
+    /// Placeholder of the value taken by this flag, if any.
+    pub value: Option<&'static str>,
+    /// Accepted values. Any value is accepted if empty.
+    pub values: &'static [&'static str],
+}
+
+const DAY: ArgSpec = ArgSpec {
+    name: "day",
+    help: "Day of the puzzle, e.g. `1` or `01`",
+    required: true,
+    values: &[],
+};
+
+const RELEASE: FlagSpec = FlagSpec {
+    name: "--release",
+    help: "Build solutions in release mode",
+    value: None,
+    values: &[],
+};
+
+const DEBUG: FlagSpec = FlagSpec {
+    name: "--debug",
+    help: "Build solutions in debug mode, even if `release` is set in aoc.toml",
+    value: None,
+    values: &[],
+};
+
+const DRY_RUN: FlagSpec = FlagSpec {
+    name: "--dry-run",
+    help: "List what would be done, without changing any files",
+    value: None,
+    values: &[],
+};
+
+/// Flags accepted by every command.
+pub static GLOBAL_FLAGS: &[FlagSpec] = &[
+    FlagSpec {
+        name: "--year",
+        help: "Year of the event, overrides aoc.toml",
+        value: Some("year"),
+        values: &[],
+    },
+    FlagSpec {
+        name: "--format",
+        help: "Output format of `solve`, `all`, `time`, `verify`, `status`, `leaderboard` and `read`",
+        value: Some("format"),
+        values: &["text", "plain", "json"],
+    },
+    FlagSpec {
+        name: "--help",
+        help: "Print help",
+        value: None,
+        values: &[],
+    },
+];
+
+pub static COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "scaffold",
+        about: "Create the solution, input and example files for a day",
+        args: &[DAY],
+        flags: &[
+            FlagSpec {
+                name: "--download",
+                help: "Download the input and puzzle description afterwards",
+                value: None,
+                values: &[],
+            },
+            FlagSpec {
+                name: "--force",
+                help: "Replace an existing file: module, input or example. Can be repeated",
+                value: Some("file"),
+                values: &["module", "input", "example"],
+            },
+            DRY_RUN,
+            FlagSpec {
+                name: "--template",
+                help: "Template of the solution: plain, grid, graph, parse-once or a user template",
+                value: Some("name"),
+                values: &[],
+            },
+        ],
+        alias: Some("scaffold"),
+    },
+    CommandSpec {
+        name: "download",
+        about: "Download the input and puzzle description of a day via aoc-cli",
+        args: &[DAY],
+        flags: &[
+            FlagSpec {
+                name: "--force",
+                help: "Download the input again, even if it exists",
+                value: Some("file"),
+                values: &["input"],
+            },
+            DRY_RUN,
+        ],
+        alias: Some("download"),
+    },
+    CommandSpec {
+        name: "read",
+        about: "Print the downloaded puzzle description of a day",
+        args: &[DAY],
+        flags: &[FlagSpec {
+            name: "--part",
+            help: "Only print part 1 or part 2",
+            value: Some("part"),
+            values: &["1", "2"],
+        }],
+        alias: Some("read"),
+    },
+    CommandSpec {
+        name: "solve",
+        about: "Run the solution of a day against its input",
+        args: &[DAY],
+        flags: &[
+            RELEASE,
+            DEBUG,
+            FlagSpec {
+                name: "--dhat",
+                help: "Profile heap allocations with DHAT",
+                value: None,
+                values: &[],
+            },
+            FlagSpec {
+                name: "--submit",
+                help: "Submit the answer of a part via aoc-cli",
+                value: Some("part"),
+                values: &[],
+            },
+            FlagSpec {
+                name: "--verbose",
+                help: "Show the debug output of the solution",
+                value: None,
+                values: &[],
+            },
+        ],
+        alias: Some("solve"),
+    },
+    CommandSpec {
+        name: "all",
+        about: "Run all solutions",
+        args: &[],
+        flags: &[RELEASE, DEBUG],
+        alias: Some("all"),
+    },
+    CommandSpec {
+        name: "time",
+        about: "Benchmark solutions, by default the ones without stored timings",
+        args: &[ArgSpec {
+            required: false,
+            help: "Only benchmark this day",
+            ..DAY
+        }],
+        flags: &[
+            FlagSpec {
+                name: "--all",
+                help: "Benchmark all solutions",
+                value: None,
+                values: &[],
+            },
+            FlagSpec {
+                name: "--store",
+                help: "Store timings and update the readme",
+                value: None,
+                values: &[],
+            },
+        ],
+        alias: Some("time"),
+    },
+    CommandSpec {
+        name: "report",
+        about: "Render stored timings as an offline HTML dashboard",
+        args: &[],
+        flags: &[FlagSpec {
+            name: "--out",
+            help: "Write the report to this path, defaults to target/report.html",
+            value: Some("path"),
+            values: &[],
+        }],
+        alias: None,
+    },
+    CommandSpec {
+        name: "verify",
+        about: "Compare answers of solutions with the known answers in data/answers",
+        args: &[ArgSpec {
+            required: false,
+            help: "Only verify this day",
+            ..DAY
+        }],
+        flags: &[
+            FlagSpec {
+                name: "--inputs",
+                help: "Only verify against real inputs",
+                value: None,
+                values: &[],
+            },
+            FlagSpec {
+                name: "--examples",
+                help: "Only verify against example inputs",
+                value: None,
+                values: &[],
+            },
+            FlagSpec {
+                name: "--junit",
+                help: "Write a JUnit XML report to this path",
+                value: Some("path"),
+                values: &[],
+            },
+            RELEASE,
+            DEBUG,
+        ],
+        alias: None,
+    },
+    CommandSpec {
+        name: "status",
+        about: "Show the progress of all days as a calendar",
+        args: &[],
+        flags: &[
+            FlagSpec {
+                name: "--no-tests",
+                help: "Do not run the tests of scaffolded days",
+                value: None,
+                values: &[],
+            },
+            FlagSpec {
+                name: "--readme",
+                help: "Write the stars to the readme",
+                value: None,
+                values: &[],
+            },
+        ],
+        alias: None,
+    },
+    CommandSpec {
+        name: "gen",
+        about: "Write a random input of a day to data/generated, using the generator of its solution",
+        args: &[DAY],
+        flags: &[
+            FlagSpec {
+                name: "--size",
+                help: "Size of the input, e.g. its number of lines. Each generator documents its meaning",
+                value: Some("size"),
+                values: &[],
+            },
+            FlagSpec {
+                name: "--seed",
+                help: "Seed of the random numbers, picked at random if not set",
+                value: Some("seed"),
+                values: &[],
+            },
+            RELEASE,
+            DEBUG,
+        ],
+        alias: None,
+    },
+    CommandSpec {
+        name: "diff",
+        about: "Compare the variants of a day's parts on random inputs and save the first disagreement",
+        args: &[DAY],
+        flags: &[
+            FlagSpec {
+                name: "--runs",
+                help: "Number of inputs to compare the variants on, defaults to 100",
+                value: Some("runs"),
+                values: &[],
+            },
+            FlagSpec {
+                name: "--size",
+                help: "Size of the largest generated input, defaults to 50",
+                value: Some("size"),
+                values: &[],
+            },
+            FlagSpec {
+                name: "--seed",
+                help: "Seed of the first input, picked at random if not set",
+                value: Some("seed"),
+                values: &[],
+            },
+            FlagSpec {
+                name: "--mutate",
+                help: "Mutate the example and the input instead of using the generator",
+                value: None,
+                values: &[],
+            },
+            RELEASE,
+            DEBUG,
+        ],
+        alias: None,
+    },
+    CommandSpec {
+        name: "scale",
+        about: "Bench a part on generated inputs of growing size and fit its complexity",
+        args: &[DAY],
+        flags: &[
+            FlagSpec {
+                name: "--part",
+                help: "The part to bench",
+                value: Some("part"),
+                values: &["1", "2"],
+            },
+            FlagSpec {
+                name: "--size",
+                help: "Size of the smallest input, which doubles with every step, defaults to 16",
+                value: Some("size"),
+                values: &[],
+            },
+            FlagSpec {
+                name: "--steps",
+                help: "Number of sizes to bench, defaults to 8",
+                value: Some("steps"),
+                values: &[],
+            },
+            FlagSpec {
+                name: "--seed",
+                help: "Seed of the inputs, picked at random if not set",
+                value: Some("seed"),
+                values: &[],
+            },
+        ],
+        alias: None,
+    },
+    CommandSpec {
+        name: "check",
+        about: "Check the input and examples of a day against the assumptions of its solution",
+        args: &[DAY],
+        flags: &[FlagSpec {
+            name: "--file",
+            help: "Also check this input, e.g. a generated one. Can be repeated",
+            value: Some("path"),
+            values: &[],
+        }],
+        alias: None,
+    },
+    CommandSpec {
+        name: "config",
+        about: "Inspect the project configuration",
+        args: &[ArgSpec {
+            name: "action",
+            help: "`show` prints the effective configuration",
+            required: true,
+            values: &["show"],
+        }],
+        flags: &[],
+        alias: None,
+    },
+    CommandSpec {
+        name: "inputs",
+        about: "Store inputs, puzzles and answers in an encrypted archive",
+        args: &[ArgSpec {
+            name: "action",
+            help: "`seal` adds the local files to the archive, `unseal` restores them",
+            required: true,
+            values: &["seal", "unseal"],
+        }],
+        flags: &[FlagSpec {
+            name: "--overwrite",
+            help: "Replace existing files when unsealing",
+            value: None,
+            values: &[],
+        }],
+        alias: None,
+    },
+    CommandSpec {
+        name: "leaderboard",
+        about: "Show the rankings and star times of a private leaderboard",
+        args: &[ArgSpec {
+            name: "id",
+            help: "Id of the leaderboard, the number at the end of its URL",
+            required: true,
+            values: &[],
+        }],
+        flags: &[FlagSpec {
+            name: "--day",
+            help: "Show the star times of this day instead of the latest one",
+            value: Some("day"),
+            values: &[],
+        }],
+        alias: None,
+    },
+    #[cfg(feature = "today")]
+    CommandSpec {
+        name: "today",
+        about: "Scaffold, download and read the current day",
+        args: &[],
+        flags: &[FlagSpec {
+            name: "--wait",
+            help: "Wait for the next puzzle to unlock, showing a countdown",
+            value: None,
+            values: &[],
+        }],
+        alias: Some("today"),
+    },
+    CommandSpec {
+        name: "completions",
+        about: "Print a shell completion script",
+        args: &[ArgSpec {
+            name: "shell",
+            help: "One of `bash`, `zsh` or `fish`",
+            required: true,
+            values: &["bash", "zsh", "fish"],
+        }],
+        flags: &[],
+        alias: None,
+    },
+    CommandSpec {
+        name: "help",
+        about: "Print help for all commands or a single command",
+        args: &[ArgSpec {
+            name: "command",
+            help: "Print help for this command",
+            required: false,
+            values: &[],
+        }],
+        flags: &[],
+        alias: None,
+    },
+];
+
+/// Returns the command with the given name.
+pub fn find_command(name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// What the command line asks for, if it is valid.
+#[derive(Debug)]
+pub enum Invocation {
+    /// Print help, either for a single command or for all of them.
+    Help(Option<&'static CommandSpec>),
+    Run(&'static CommandSpec),
+}
+
+impl std::fmt::Debug for CommandSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CommandSpec({})", self.name)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    NoCommand,
+    UnknownCommand {
+        name: String,
+        suggestion: Option<&'static str>,
+    },
+    UnknownFlag {
+        command: &'static str,
+        flag: String,
+        suggestion: Option<&'static str>,
+    },
+    MissingValue {
+        flag: &'static str,
+    },
+    MissingArgument {
+        command: &'static str,
+        arg: &'static str,
+    },
+    InvalidValue {
+        arg: &'static str,
+        value: String,
+        suggestion: Option<&'static str>,
+    },
+    UnexpectedArgument {
+        command: &'static str,
+        value: String,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let did_you_mean =
+            |f: &mut std::fmt::Formatter<'_>, suggestion: &Option<&str>| match suggestion {
+                Some(s) => write!(f, " Did you mean `{s}`?"),
+                None => Ok(()),
+            };
+
+        match self {
+            Error::NoCommand => write!(f, "no command specified."),
+            Error::UnknownCommand { name, suggestion } => {
+                write!(f, "unknown command `{name}`.")?;
+                did_you_mean(f, suggestion)
+            }
+            Error::UnknownFlag {
+                command,
+                flag,
+                suggestion,
+            } => {
+                write!(f, "unknown flag `{flag}` for `{command}`.")?;
+                did_you_mean(f, suggestion)
+            }
+            Error::MissingValue { flag } => write!(f, "`{flag}` requires a value."),
+            Error::MissingArgument { command, arg } => {
+                write!(f, "`{command}` requires the argument <{arg}>.")
+            }
+            Error::InvalidValue {
+                arg,
+                value,
+                suggestion,
+            } => {
+                write!(f, "invalid value `{value}` for `{arg}`.")?;
+                did_you_mean(f, suggestion)
+            }
+            Error::UnexpectedArgument { command, value } => {
+                write!(f, "unexpected argument `{value}` for `{command}`.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Checks the command line (without the binary name) against the command table.
+/// Values are parsed by the caller once the command line is known to be valid.
+pub fn validate(args: &[String]) -> Result<Invocation, Error> {
+    let mut command: Option<&'static CommandSpec> = None;
+    let mut positionals: Vec<&str> = vec![];
+    let mut help = false;
+
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        if arg == "-h" || arg == "--help" {
+            help = true;
+            continue;
+        }
+
+        if arg.starts_with('-') && arg != "-" {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (arg.as_str(), None),
+            };
+
+            let flags = command.map_or(&[][..], |c| c.flags);
+            let Some(spec) = flags.iter().chain(GLOBAL_FLAGS).find(|f| f.name == name) else {
+                let candidates = flags.iter().chain(GLOBAL_FLAGS).map(|f| f.name);
+                return Err(Error::UnknownFlag {
+                    command: command.map_or(BIN_NAME, |c| c.name),
+                    flag: name.to_string(),
+                    suggestion: suggest(name, candidates),
+                });
+            };
+
+            if spec.value.is_some() {
+                let Some(value) = inline_value.or_else(|| iter.next().map(String::as_str)) else {
+                    return Err(Error::MissingValue { flag: spec.name });
+                };
+
+                if !spec.values.is_empty() && !spec.values.contains(&value) {
+                    return Err(Error::InvalidValue {
+                        arg: spec.name,
+                        value: value.to_string(),
+                        suggestion: suggest(value, spec.values.iter().copied()),
+                    });
+                }
+            }
+
+            continue;
+        }
+
+        match command {
+            None => {
+                command = Some(find_command(arg).ok_or_else(|| Error::UnknownCommand {
+                    name: arg.clone(),
+                    suggestion: suggest(arg, COMMANDS.iter().map(|c| c.name)),
+                })?);
+            }
+            Some(_) => positionals.push(arg),
+        }
+    }
+
+    let Some(command) = command else {
+        return if help {
+            Ok(Invocation::Help(None))
+        } else {
+            Err(Error::NoCommand)
+        };
+    };
+
+    if help {
+        return Ok(Invocation::Help(Some(command)));
+    }
+
+    if command.name == "help" {
+        return match positionals.as_slice() {
+            [] => Ok(Invocation::Help(None)),
+            [name] => find_command(name)
+                .map(|c| Invocation::Help(Some(c)))
+                .ok_or_else(|| Error::UnknownCommand {
+                    name: (*name).to_string(),
+                    suggestion: suggest(name, COMMANDS.iter().map(|c| c.name)),
+                }),
+            [_, value, ..] => Err(Error::UnexpectedArgument {
+                command: command.name,
+                value: (*value).to_string(),
+            }),
+        };
+    }
+
+    if let Some(value) = positionals.get(command.args.len()) {
+        return Err(Error::UnexpectedArgument {
+            command: command.name,
+            value: (*value).to_string(),
+        });
+    }
+
+    for (i, spec) in command.args.iter().enumerate() {
+        match positionals.get(i) {
+            None if spec.required => {
+                return Err(Error::MissingArgument {
+                    command: command.name,
+                    arg: spec.name,
+                });
+            }
+            Some(value) if !spec.values.is_empty() && !spec.values.contains(value) => {
+                return Err(Error::InvalidValue {
+                    arg: spec.name,
+                    value: (*value).to_string(),
+                    suggestion: suggest(value, spec.values.iter().copied()),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Invocation::Run(command))
+}
+
+/// Returns the candidate closest to `input`, if it is close enough to be a typo.
+fn suggest(input: &str, candidates: impl Iterator<Item = &'static str>) -> Option<&'static str> {
+    let max_distance = (input.chars().count() / 3).max(2);
+
+    candidates
+        .map(|c| (levenshtein(input, c), c))
+        .filter(|(d, _)| *d <= max_distance)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn usage(command: &CommandSpec) -> String {
+    let mut usage = match command.alias {
+        Some(alias) => format!("cargo {alias}"),
+        None => format!("cargo run --quiet -- {}", command.name),
+    };
+
+    for arg in command.args {
+        let placeholder = if arg.values.is_empty() {
+            arg.name.to_string()
+        } else {
+            arg.values.join("|")
+        };
+
+        if arg.required {
+            let _ = write!(usage, " <{placeholder}>");
+        } else {
+            let _ = write!(usage, " [{placeholder}]");
+        }
+    }
+
+    if !command.flags.is_empty() {
+        usage.push_str(" [options]");
+    }
+
+    usage
+}
+
+fn flag_label(flag: &FlagSpec) -> String {
+    match flag.value {
+        Some(value) => format!("{} <{value}>", flag.name),
+        None => flag.name.to_string(),
+    }
+}
+
+/// Help text for a single command, or an overview of all commands.
+pub fn help(command: Option<&CommandSpec>) -> String {
+    let mut out = String::new();
+
+    let Some(command) = command else {
+        out.push_str("Advent of Code template CLI.\n\n");
+        let _ = writeln!(
+            out,
+            "Usage: cargo <command> [options] (or `cargo run --quiet -- <command>`)\n"
+        );
+        out.push_str("Commands:\n");
+        for command in COMMANDS {
+            let _ = writeln!(out, "  {:<14}{}", command.name, command.about);
+        }
+        out.push_str("\nGlobal options:\n");
+        for flag in GLOBAL_FLAGS {
+            let _ = writeln!(out, "  {:<20}{}", flag_label(flag), flag.help);
+        }
+        out.push_str("\nRun `cargo run --quiet -- help <command>` for help on a command.\n");
+        return out;
+    };
+
+    let _ = writeln!(out, "{}\n", command.about);
+    let _ = writeln!(out, "Usage: {}", usage(command));
+
+    if !command.args.is_empty() {
+        out.push_str("\nArguments:\n");
+        for arg in command.args {
+            let _ = writeln!(out, "  {:<20}{}", format!("<{}>", arg.name), arg.help);
+        }
+    }
+
+    out.push_str("\nOptions:\n");
+    for flag in command.flags.iter().chain(GLOBAL_FLAGS) {
+        let _ = writeln!(out, "  {:<20}{}", flag_label(flag), flag.help);
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(format!("unsupported shell `{s}`.")),
+        }
+    }
+}
+
+/// Escapes a string for use inside single quotes.
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Words that can follow a command: its flags, the global flags and the values of its arguments.
+fn completion_words(command: &CommandSpec) -> Vec<&'static str> {
+    let values: Vec<&'static str> = if command.name == "help" {
+        COMMANDS.iter().map(|c| c.name).collect()
+    } else {
+        command
+            .args
+            .iter()
+            .flat_map(|a| a.values)
+            .copied()
+            .collect()
+    };
+
+    values
+        .into_iter()
+        .chain(command.flags.iter().chain(GLOBAL_FLAGS).map(|f| f.name))
+        .collect()
+}
+
+/// Generates a completion script for the binary of the template CLI.
+pub fn completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash_completions(),
+        Shell::Zsh => zsh_completions(),
+        Shell::Fish => fish_completions(),
+    }
+}
+
+fn bash_completions() -> String {
+    let function = format!("_{BIN_NAME}");
+    let mut out = String::new();
+
+    let _ = writeln!(out, "{function}() {{");
+    out.push_str("    local cur=\"${COMP_WORDS[COMP_CWORD]}\"\n");
+    out.push_str("    if [ \"$COMP_CWORD\" -eq 1 ]; then\n");
+    let names: Vec<_> = COMMANDS.iter().map(|c| c.name).collect();
+    let _ = writeln!(
+        out,
+        "        COMPREPLY=($(compgen -W {} -- \"$cur\"))",
+        quote(&names.join(" "))
+    );
+    out.push_str("        return\n    fi\n\n");
+    out.push_str("    local words\n    case \"${COMP_WORDS[1]}\" in\n");
+    for command in COMMANDS {
+        let _ = writeln!(
+            out,
+            "        {}) words={} ;;",
+            command.name,
+            quote(&completion_words(command).join(" "))
+        );
+    }
+    out.push_str("        *) words='' ;;\n    esac\n");
+    out.push_str("    COMPREPLY=($(compgen -W \"$words\" -- \"$cur\"))\n}\n\n");
+    let _ = writeln!(out, "complete -F {function} {BIN_NAME}");
+
+    out
+}
+
+fn zsh_completions() -> String {
+    let function = format!("_{BIN_NAME}");
+    let mut out = String::new();
+
+    let _ = writeln!(out, "#compdef {BIN_NAME}\n");
+    let _ = writeln!(out, "{function}() {{");
+    // `words` is reserved by zsh for the current command line.
+    out.push_str("    local -a commands candidates\n    commands=(\n");
+    for command in COMMANDS {
+        let _ = writeln!(
+            out,
+            "        {}",
+            quote(&format!("{}:{}", command.name, command.about))
+        );
+    }
+    out.push_str("    )\n\n");
+    out.push_str("    if (( CURRENT == 2 )); then\n");
+    out.push_str("        _describe 'command' commands\n        return\n    fi\n\n");
+    out.push_str("    case $words[2] in\n");
+    for command in COMMANDS {
+        let candidates: Vec<String> = completion_words(command)
+            .into_iter()
+            .map(|w| {
+                let help = command
+                    .flags
+                    .iter()
+                    .chain(GLOBAL_FLAGS)
+                    .find(|f| f.name == w)
+                    .map(|f| f.help)
+                    .or_else(|| find_command(w).map(|c| c.about))
+                    .unwrap_or_else(|| {
+                        command
+                            .args
+                            .iter()
+                            .find(|a| a.values.contains(&w))
+                            .map_or("", |a| a.help)
+                    });
+                quote(&format!("{w}:{help}"))
+            })
+            .collect();
+        let _ = writeln!(
+            out,
+            "        {}) candidates=({}) ;;",
+            command.name,
+            candidates.join(" ")
+        );
+    }
+    out.push_str("        *) candidates=() ;;\n    esac\n\n");
+    out.push_str("    _describe 'argument' candidates\n}\n\n");
+    let _ = writeln!(out, "{function} \"$@\"");
+
+    out
+}
+
+/// Options of a fish completion for a flag that takes a value.
+fn fish_flag_value(flag: &FlagSpec) -> String {
+    match (flag.value, flag.values) {
+        (None, _) => String::new(),
+        (Some(_), []) => " -r".into(),
+        (Some(_), values) => format!(" -x -a {}", quote(&values.join(" "))),
+    }
+}
+
+fn fish_completions() -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "complete -c {BIN_NAME} -f");
+
+    for flag in GLOBAL_FLAGS {
+        let _ = writeln!(
+            out,
+            "complete -c {BIN_NAME} -l {}{} -d {}",
+            flag.name.trim_start_matches("--"),
+            fish_flag_value(flag),
+            quote(flag.help)
+        );
+    }
+
+    for command in COMMANDS {
+        let _ = writeln!(
+            out,
+            "complete -c {BIN_NAME} -n __fish_use_subcommand -a {} -d {}",
+            command.name,
+            quote(command.about)
+        );
+
+        let condition = quote(&format!("__fish_seen_subcommand_from {}", command.name));
+
+        for flag in command.flags {
+            let _ = writeln!(
+                out,
+                "complete -c {BIN_NAME} -n {condition} -l {}{} -d {}",
+                flag.name.trim_start_matches("--"),
+                fish_flag_value(flag),
+                quote(flag.help)
+            );
+        }
+
+        let values: Vec<&str> = if command.name == "help" {
+            COMMANDS.iter().map(|c| c.name).collect()
+        } else {
+            command
+                .args
+                .iter()
+                .flat_map(|a| a.values)
+                .copied()
+                .collect()
+        };
+
+        if !values.is_empty() {
+            let _ = writeln!(
+                out,
+                "complete -c {BIN_NAME} -n {condition} -a {}",
+                quote(&values.join(" "))
+            );
+        }
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Error, Invocation, Shell, completions, find_command, help, validate};
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn validates_commands() {
+        assert!(matches!(
+            validate(&args("solve 1 --release --submit 2")),
+            Ok(Invocation::Run(c)) if c.name == "solve"
+        ));
+        assert!(matches!(
+            validate(&args("--year 2024 config show")),
+            Ok(Invocation::Run(c)) if c.name == "config"
+        ));
+        assert!(matches!(
+            validate(&args("time --store")),
+            Ok(Invocation::Run(c)) if c.name == "time"
+        ));
+        assert!(matches!(
+            validate(&args("solve 1 --submit=1")),
+            Ok(Invocation::Run(_))
+        ));
+    }
+
+    #[test]
+    fn handles_help() {
+        assert!(matches!(
+            validate(&args("--help")),
+            Ok(Invocation::Help(None))
+        ));
+        assert!(matches!(
+            validate(&args("help")),
+            Ok(Invocation::Help(None))
+        ));
+        assert!(matches!(
+            validate(&args("help solve")),
+            Ok(Invocation::Help(Some(c))) if c.name == "solve"
+        ));
+        assert!(matches!(
+            validate(&args("time -h")),
+            Ok(Invocation::Help(Some(c))) if c.name == "time"
+        ));
+
+        let text = help(find_command("solve"));
+        assert!(text.contains("Usage: cargo solve <day> [options]"));
+        assert!(text.contains("--submit <part>"));
+        assert!(text.contains("--year <year>"));
+    }
+
+    #[test]
+    fn suggests_commands_and_flags() {
+        assert_eq!(
+            validate(&args("slove 1")).unwrap_err(),
+            Error::UnknownCommand {
+                name: "slove".into(),
+                suggestion: Some("solve"),
+            }
+        );
+        assert_eq!(
+            validate(&args("solve 1 --relase")).unwrap_err(),
+            Error::UnknownFlag {
+                command: "solve",
+                flag: "--relase".into(),
+                suggestion: Some("--release"),
+            }
+        );
+        assert_eq!(
+            validate(&args("config shw")).unwrap_err(),
+            Error::InvalidValue {
+                arg: "action",
+                value: "shw".into(),
+                suggestion: Some("show"),
+            }
+        );
+        assert_eq!(
+            validate(&args("xyz")).unwrap_err(),
+            Error::UnknownCommand {
+                name: "xyz".into(),
+                suggestion: None,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert_eq!(validate(&args("")).unwrap_err(), Error::NoCommand);
+        assert_eq!(
+            validate(&args("solve")).unwrap_err(),
+            Error::MissingArgument {
+                command: "solve",
+                arg: "day",
+            }
+        );
+        assert_eq!(
+            validate(&args("solve 1 2")).unwrap_err(),
+            Error::UnexpectedArgument {
+                command: "solve",
+                value: "2".into(),
+            }
+        );
+        assert_eq!(
+            validate(&args("solve 1 --submit")).unwrap_err(),
+            Error::MissingValue { flag: "--submit" }
+        );
+        assert!(matches!(
+            validate(&args("all --store")).unwrap_err(),
+            Error::UnknownFlag { .. }
+        ));
+        assert_eq!(
+            validate(&args("all --format jsn")).unwrap_err(),
+            Error::InvalidValue {
+                arg: "--format",
+                value: "jsn".into(),
+                suggestion: Some("json"),
+            }
+        );
+    }
+
+    #[test]
+    fn generates_completions() {
+        let bash = completions(Shell::Bash);
+        assert!(bash.contains("complete -F _advent_of_code advent_of_code"));
+        assert!(bash.contains(
+            "solve) words='--release --debug --dhat --submit --verbose --year --format --help' ;;"
+        ));
+
+        let zsh = completions(Shell::Zsh);
+        assert!(zsh.starts_with("#compdef advent_of_code"));
+        assert!(zsh.contains("'config:Inspect the project configuration'"));
+
+        let fish = completions(Shell::Fish);
+        assert!(fish.contains("-n '__fish_seen_subcommand_from time' -l store"));
+        assert!(fish.contains("-n '__fish_seen_subcommand_from completions' -a 'bash zsh fish'"));
+    }
+}
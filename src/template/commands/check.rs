@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+use std::process::{self, Command};
+
+use crate::template::Day;
+
+/// Validates the input, the examples and `files` against the assumptions of a day, see
+/// `template::assumptions`. Exits with the status of the solution, which is non-zero if an assumption is violated.
+pub fn handle(day: Day, files: &[PathBuf]) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--assumptions".to_string(),
+    ];
+    for file in files {
+        cmd_args.push("--file".to_string());
+        cmd_args.push(file.display().to_string());
+    }
+
+    let status = match Command::new("cargo").args(&cmd_args).status() {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("Failed to check the assumptions of day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
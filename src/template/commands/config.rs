@@ -0,0 +1,9 @@
+use crate::template::config;
+
+pub fn handle() {
+    println!(
+        "# effective configuration, including `{}` and command-line overrides.",
+        config::CONFIG_FILE_PATH
+    );
+    println!("{}", config::get());
+}
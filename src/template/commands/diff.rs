@@ -0,0 +1,57 @@
+use std::process::{self, Command};
+
+use crate::template::Day;
+
+pub const DEFAULT_RUNS: u64 = 100;
+pub const DEFAULT_SIZE: usize = 50;
+
+/// Runs the differential tests of a day, see `template::differential`. Exits with the status of the solution,
+/// which is non-zero if the variants disagree. Solutions are built with the `checked` feature, so that unchecked
+/// accesses panic on inputs that break their assumptions, see `advent_of_code::unchecked`. Optimized builds use
+/// the `diff` profile, the release profile with `panic = "unwind"`, so that these panics can be caught.
+pub fn handle(day: Day, runs: u64, size: usize, seed: Option<u64>, mutate: bool, release: bool) {
+    if runs == 0 {
+        eprintln!("`--runs` must be at least 1.");
+        process::exit(1);
+    }
+
+    let seed = seed.unwrap_or_else(super::generate::random_seed);
+
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--features".to_string(),
+        "checked".to_string(),
+    ];
+    if release {
+        cmd_args.push("--profile".to_string());
+        cmd_args.push("diff".to_string());
+    }
+    cmd_args.extend([
+        "--".to_string(),
+        "--differential".to_string(),
+        "--runs".to_string(),
+        runs.to_string(),
+        "--size".to_string(),
+        size.to_string(),
+        "--seed".to_string(),
+        seed.to_string(),
+    ]);
+    if mutate {
+        cmd_args.push("--mutate".to_string());
+    }
+
+    let status = match Command::new("cargo").args(&cmd_args).status() {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("Failed to run the variants of day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
@@ -1,7 +1,24 @@
-use crate::template::{Day, aoc_cli};
-use std::process;
+use crate::template::aoc_cli::{self, AocCommandError, Download};
+use crate::template::commands::scaffold::Artifact;
+use crate::template::{Day, config};
+use std::{fs, path::Path, process};
+
+/// Downloads the puzzle description, and the input unless it was downloaded before.
+/// The description is always replaced, as it changes once part one is solved.
+pub fn handle(day: Day, force: &[Artifact], dry_run: bool) {
+    let (input_path, puzzle_path) = paths(day);
+    let keep_input = keep_input(day, force);
+
+    if dry_run {
+        if keep_input {
+            println!("Would keep existing input file \"{input_path}\"");
+        } else {
+            println!("Would download input to \"{input_path}\"");
+        }
+        println!("Would download puzzle to \"{puzzle_path}\"");
+        return;
+    }
 
-pub fn handle(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!(
             "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
@@ -9,8 +26,41 @@ pub fn handle(day: Day) {
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
+    if let Err(e) = download(day, force) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
 }
+
+/// Calls aoc-cli, see [`handle`].
+pub fn download(day: Day, force: &[Artifact]) -> Result<(), AocCommandError> {
+    let (input_path, puzzle_path) = paths(day);
+
+    let files = if keep_input(day, force) {
+        println!(
+            "Kept existing input file \"{input_path}\". Pass `--force input` to download it again."
+        );
+        Download::PuzzleOnly
+    } else {
+        Download::Both
+    };
+
+    // `scaffold` creates an empty input file, which is replaced like an outdated puzzle.
+    let overwrite = Path::new(&puzzle_path).exists()
+        || (files == Download::Both && Path::new(&input_path).exists());
+
+    aoc_cli::download(day, files, overwrite).map(|_| ())
+}
+
+fn paths(day: Day) -> (String, String) {
+    let data = &config::get().data;
+    (
+        format!("{}/{day}.txt", data.inputs),
+        format!("{}/{day}.md", data.puzzles),
+    )
+}
+
+/// Inputs never change, so a downloaded input is kept unless it is forced.
+fn keep_input(day: Day, force: &[Artifact]) -> bool {
+    !force.contains(&Artifact::Input) && fs::metadata(paths(day).0).is_ok_and(|m| m.len() > 0)
+}
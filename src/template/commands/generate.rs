@@ -0,0 +1,78 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+
+use crate::template::{Day, config};
+
+/// Runs the generator of a day and writes the input to `data/generated/{day}-{size}-{seed}.txt`.
+/// A random seed is picked if none is passed; it is part of the file name so the input can be reproduced.
+pub fn handle(day: Day, size: usize, seed: Option<u64>, release: bool) {
+    let seed = seed.unwrap_or_else(random_seed);
+    let path = path(day, size, seed);
+
+    let input = match generate(day, size, seed, release) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Failed to generate an input for day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) =
+        fs::create_dir_all(path.parent().unwrap()).and_then(|()| fs::write(&path, &input))
+    {
+        eprintln!("Failed to write {}: {e}", path.display());
+        process::exit(1);
+    }
+
+    println!(
+        "Generated {} ({} lines) with `--size {size} --seed {seed}`.",
+        path.display(),
+        input.lines().count()
+    );
+}
+
+pub fn path(day: Day, size: usize, seed: u64) -> PathBuf {
+    Path::new(&config::get().data_dir("generated")).join(format!("{day}-{size}-{seed}.txt"))
+}
+
+/// Runs the solution of a day with `--generate` and returns what it printed.
+pub fn generate(day: Day, size: usize, seed: u64, release: bool) -> Result<String, String> {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+    ];
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+    cmd_args.extend([
+        "--".to_string(),
+        "--generate".to_string(),
+        "--size".to_string(),
+        size.to_string(),
+        "--seed".to_string(),
+        seed.to_string(),
+    ]);
+
+    let output = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("could not run cargo: {e}"))?;
+
+    if !output.status.success() {
+        return Err("the generator failed.".into());
+    }
+
+    String::from_utf8(output.stdout).map_err(|_| "the generator did not print UTF-8.".into())
+}
+
+pub(crate) fn random_seed() -> u64 {
+    let mut bytes = [0u8; 8];
+    getrandom::fill(&mut bytes).expect("failed to pick a random seed");
+    // short seeds are easier to pass around.
+    u64::from_le_bytes(bytes) % 1_000_000
+}
@@ -0,0 +1,55 @@
+use std::process;
+
+use crate::template::{config, sealed};
+
+pub fn handle_seal() {
+    let sealed = match sealed::seal() {
+        Ok(sealed) => sealed,
+        Err(e) => {
+            eprintln!("Failed to seal inputs: {e}");
+            process::exit(1);
+        }
+    };
+
+    let config = &config::get().seal;
+
+    if sealed.created_key {
+        println!(
+            "🔑 Created key file \"{}\". Keep a copy of it, the archive cannot be opened without it.",
+            config.key_file
+        );
+    }
+
+    if sealed.changed.is_empty() {
+        println!("🔒 \"{}\" is up to date.", config.archive);
+        return;
+    }
+
+    for name in &sealed.changed {
+        println!("Sealed \"{name}\"");
+    }
+    println!("---");
+    println!(
+        "🔒 Wrote {} files to \"{}\". Commit it to share your inputs between machines.",
+        sealed.total, config.archive
+    );
+}
+
+pub fn handle_unseal(overwrite: bool) {
+    match sealed::unseal(overwrite) {
+        Ok(written) if written.is_empty() => {
+            println!("🔓 Nothing to unseal, all files exist. Pass `--overwrite` to replace them.");
+        }
+        Ok(written) => {
+            for path in &written {
+                println!("Unsealed \"{path}\"");
+            }
+            println!("---");
+            println!("🔓 Restored {} files.", written.len());
+        }
+        Err(e) => {
+            eprintln!("Failed to unseal inputs: {e}");
+            process::exit(1);
+        }
+    }
+}
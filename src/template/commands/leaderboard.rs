@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use std::{env, fs, process};
+
+use crate::template::aoc_cli::AocCommandError;
+use crate::template::leaderboard;
+use crate::template::render::{self, Format};
+use crate::template::{Day, aoc_cli, config};
+
+/// The site asks to not request a leaderboard more often than this.
+const MIN_REFRESH: Duration = Duration::from_secs(15 * 60);
+
+pub fn handle(id: u64, day: Option<Day>) {
+    let Some(year) = config::get().year else {
+        eprintln!("Could not determine the year. Set `year` in aoc.toml or pass `--year`.");
+        process::exit(1);
+    };
+
+    let json = fetch(year, id);
+
+    if render::format() == Format::Json {
+        println!("{}", json.trim_end());
+        return;
+    }
+
+    let board = match leaderboard::parse(&json) {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("Failed to parse leaderboard {id}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let color =
+        render::format() == Format::Text && !env::var("NO_COLOR").is_ok_and(|s| !s.is_empty());
+    print!(
+        "{}",
+        leaderboard::render(&board, id, day.map(Day::into_inner), color)
+    );
+}
+
+fn cache_path(year: u16, id: u64) -> PathBuf {
+    Path::new(&config::get().data_dir("leaderboards")).join(format!("{year}-{id}.json"))
+}
+
+/// Returns the cached leaderboard if it is recent enough, and requests it otherwise.
+/// Falls back to an outdated cache if the request fails.
+fn fetch(year: u16, id: u64) -> String {
+    let path = cache_path(year, id);
+    let cached = fs::read_to_string(&path).ok();
+    let age = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+
+    if let (Some(json), Some(age)) = (&cached, age)
+        && age < MIN_REFRESH
+    {
+        eprintln!(
+            "Showing the leaderboard as of {} minute(s) ago. It is refreshed at most every {} minutes.",
+            age.as_secs() / 60,
+            MIN_REFRESH.as_secs() / 60
+        );
+        return json.clone();
+    }
+
+    let fetched = aoc_cli::leaderboard(year, id)
+        .map_err(|e| match e {
+            AocCommandError::CommandNotCallable => "curl could not be called.".to_string(),
+            AocCommandError::BadExitStatus(_) => "the request failed.".to_string(),
+            e => e.to_string(),
+        })
+        .and_then(|output| {
+            let json = String::from_utf8_lossy(&output.stdout).to_string();
+            // an invalid session is answered with a redirect to the login page, not an error.
+            leaderboard::parse(&json).map(|_| json).map_err(|_| {
+                "the response is not a leaderboard. Is the session cookie valid, and are you a member?"
+                    .to_string()
+            })
+        });
+
+    match (fetched, cached) {
+        (Ok(json), _) => {
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            if let Err(e) = fs::write(&path, &json) {
+                eprintln!(
+                    "Warning: could not cache the leaderboard in \"{}\": {e}",
+                    path.display()
+                );
+            }
+            json
+        }
+        (Err(e), Some(json)) => {
+            eprintln!("Warning: failed to fetch leaderboard {id}: {e} Showing the cached copy.");
+            json
+        }
+        (Err(e), None) => {
+            eprintln!("Failed to fetch leaderboard {id}: {e}");
+            process::exit(1);
+        }
+    }
+}
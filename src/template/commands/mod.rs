@@ -1,6 +1,18 @@
 pub mod all;
+pub mod check;
+pub mod config;
+pub mod diff;
 pub mod download;
+pub mod generate;
+pub mod inputs;
+pub mod leaderboard;
 pub mod read;
+pub mod report;
 pub mod scaffold;
+pub mod scale;
 pub mod solve;
+pub mod status;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
+pub mod verify;
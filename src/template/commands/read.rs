@@ -1,17 +1,89 @@
-use std::process;
+use std::io::{IsTerminal, Write, stdout};
+use std::process::{self, Command, Stdio};
+use std::{env, fs};
 
-use crate::template::{Day, aoc_cli};
+use crate::template::markdown::{self, Style};
+use crate::template::render::{self, Format};
+use crate::template::{Day, aoc_cli, config};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
+/// Wrapped paragraphs are not wider than this, even in wide terminals.
+const MAX_WIDTH: usize = 100;
+
+pub fn handle(day: Day, part: Option<u8>) {
+    let path = format!("{}/{day}.md", config::get().data_dir("puzzles"));
+
+    let Ok(puzzle) = fs::read_to_string(&path) else {
+        // without a downloaded description, fall back to fetching it.
+        if part.is_none() && aoc_cli::check().is_ok() {
+            if let Err(e) = aoc_cli::read(day) {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(1);
+            }
+            return;
+        }
+
+        eprintln!("Could not find \"{path}\". Download it with `cargo download {day}`.");
         process::exit(1);
+    };
+
+    let start = markdown::part_two_start(&puzzle);
+    let section = match (part, start) {
+        (Some(1), Some(start)) => &puzzle[..start],
+        (Some(2), Some(start)) => &puzzle[start..],
+        (Some(2), None) => {
+            eprintln!(
+                "Part two of day {day} is not unlocked in \"{path}\". Solve part one and download the puzzle again."
+            );
+            process::exit(1);
+        }
+        _ => &puzzle,
+    };
+
+    let (columns, rows) = terminal_size();
+    let style = Style {
+        width: columns.min(MAX_WIDTH),
+        color: render::format() == Format::Text
+            && !env::var("NO_COLOR").is_ok_and(|s| !s.is_empty()),
+    };
+
+    let text = markdown::render(section, &style);
+
+    if stdout().is_terminal() && text.lines().count() > rows && page(&text) {
+        return;
     }
+    print!("{text}");
+}
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+/// The size of the terminal from `COLUMNS` and `LINES`, if the shell exports them.
+fn terminal_size() -> (usize, usize) {
+    let var = |name: &str, default: usize| {
+        env::var(name)
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(default)
+    };
+    (var("COLUMNS", 80), var("LINES", 24))
+}
+
+/// Shows text in `$PAGER`, or `less`. Returns false if no pager could be started.
+fn page(text: &str) -> bool {
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less -R".into());
+    let mut args = pager.split_whitespace();
+    let Some(program) = args.next() else {
+        return false;
     };
+
+    let Ok(mut child) = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+    else {
+        return false;
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // the pager closes its input if it is quit early.
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait().is_ok()
 }
@@ -0,0 +1,92 @@
+use std::path::Path;
+use std::process::{self, Command};
+use std::{fs, io};
+
+use crate::template::config;
+use crate::template::report::{Report, Snapshot, dedup_history};
+use crate::template::timings::Timings;
+
+pub fn handle(out: &str) {
+    let timings = Timings::read_from_file();
+
+    // uncommitted timings are the latest point of the history.
+    let mut history = read_history();
+    history.push(Snapshot {
+        label: "working tree".into(),
+        timings: timings.clone(),
+    });
+    let history = dedup_history(history);
+
+    let environment = read_environment();
+
+    let title = match config::get().year {
+        Some(year) => format!("Advent of Code {year} · Benchmarks"),
+        None => "Advent of Code · Benchmarks".into(),
+    };
+
+    let html = Report {
+        title,
+        timings: &timings,
+        history: &history,
+        environment: &environment,
+    }
+    .to_html();
+
+    if let Err(e) = write(out, &html) {
+        eprintln!("Failed to write report to \"{out}\": {e}");
+        process::exit(1);
+    }
+
+    println!("Wrote benchmark report to \"{out}\".");
+}
+
+fn write(out: &str, html: &str) -> io::Result<()> {
+    if let Some(parent) = Path::new(out).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(out, html)
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+/// Reads every committed version of the timings file, oldest first.
+/// Returns no history if git is not available or the file was never committed.
+fn read_history() -> Vec<Snapshot> {
+    let path = &config::get().data.timings;
+
+    let Some(log) = git(&["log", "--reverse", "--format=%h%x09%cs", "--", path]) else {
+        return vec![];
+    };
+
+    log.lines()
+        .filter_map(|line| {
+            let (hash, date) = line.split_once('\t')?;
+            let json = git(&["show", &format!("{hash}:{path}")])?;
+            Some(Snapshot {
+                label: format!("{hash} ({date})"),
+                timings: Timings::try_from(json).ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Describes where the stored timings come from.
+fn read_environment() -> Vec<(String, String)> {
+    let path = &config::get().data.timings;
+
+    git(&["log", "-1", "--format=%h%x09%cs", "--", path])
+        .and_then(|line| {
+            let (hash, date) = line.trim().split_once('\t')?;
+            Some(vec![
+                ("Stored in commit".to_string(), hash.to_string()),
+                ("Stored on".to_string(), date.to_string()),
+            ])
+        })
+        .unwrap_or_default()
+}
@@ -1,13 +1,15 @@
 use std::{
-    fs::{File, OpenOptions},
+    fmt::Display,
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
+    str::FromStr,
 };
 
-use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::answers::Answers;
+use crate::template::templates::{self, Placeholders};
+use crate::template::{Day, config};
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -19,61 +21,153 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// A file created by `scaffold`, named by `--force` to replace it if it exists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Artifact {
+    Module,
+    Input,
+    Example,
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+impl Display for Artifact {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Artifact::Module => "module",
+            Artifact::Input => "input",
+            Artifact::Example => "example",
+        })
+    }
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+impl FromStr for Artifact {
+    type Err = String;
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "module" => Ok(Artifact::Module),
+            "input" => Ok(Artifact::Input),
+            "example" => Ok(Artifact::Example),
+            _ => Err(format!(
+                "unknown file `{s}`, expected module, input or example."
+            )),
         }
     }
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+/// What scaffolding does with a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Create,
+    Overwrite,
+    Keep,
+}
+
+impl Action {
+    /// Existing files are kept unless their artifact is forced.
+    pub fn new(path: &str, artifact: Artifact, force: &[Artifact]) -> Self {
+        match (Path::new(path).exists(), force.contains(&artifact)) {
+            (false, _) => Action::Create,
+            (true, true) => Action::Overwrite,
+            (true, false) => Action::Keep,
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    /// Describes the action, e.g. `Created module file "src/bin/01.rs"`.
+    fn describe(self, artifact: Artifact, path: &str, dry_run: bool) -> String {
+        let what = format!("{artifact} file");
+        // data files are created as empty placeholders.
+        let (created, replaced) = match artifact {
+            Artifact::Module => (what.clone(), what.clone()),
+            Artifact::Input | Artifact::Example => (
+                format!("empty {what}"),
+                format!("{what} with an empty file"),
+            ),
+        };
+
+        match (self, dry_run) {
+            (Action::Create, false) => format!("Created {created} \"{path}\""),
+            (Action::Create, true) => format!("Would create {created} \"{path}\""),
+            (Action::Overwrite, false) => format!("Replaced {replaced} \"{path}\""),
+            (Action::Overwrite, true) => format!("Would replace {replaced} \"{path}\""),
+            (Action::Keep, false) => {
+                format!("Kept existing {what} \"{path}\". Pass `--force {artifact}` to replace it.")
+            }
+            (Action::Keep, true) => format!("Would keep existing {what} \"{path}\""),
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    }
+}
+
+/// Creates or replaces a file according to its action, exiting if it cannot be written.
+fn apply(action: Action, artifact: Artifact, path: &str, content: impl FnOnce() -> String) {
+    if action == Action::Keep {
+        return;
+    }
+
+    let result = safe_create_file(path, action == Action::Overwrite)
+        .and_then(|mut file| file.write_all(content().as_bytes()));
+
+    if let Err(e) = result {
+        eprintln!("Failed to write {artifact} file \"{path}\": {e}");
+        process::exit(1);
+    }
+}
+
+/// Fills in what is already known about the day, e.g. when re-scaffolding after `download`.
+fn placeholders(day: Day) -> Placeholders {
+    let config = config::get();
+
+    let title = fs::read_to_string(format!("{}/{day}.md", config.data_dir("puzzles")))
+        .ok()
+        .and_then(|puzzle| templates::puzzle_title(&puzzle));
+
+    let answers = Answers::read(day).unwrap_or_default();
+    let example_answer = |part: u8| answers.get("example", part).map(String::from);
+
+    Placeholders {
+        day,
+        year: config.year,
+        title,
+        example_answers: [example_answer(1), example_answer(2)],
+    }
+}
+
+/// Creates the solution, input and example files of a day. Existing files are kept, unless their
+/// artifact is passed in `force`. With `dry_run`, only lists what would be done.
+pub fn handle(day: Day, force: &[Artifact], dry_run: bool, template: Option<&str>) {
+    let config = config::get();
+    let data = &config.data;
+    let input_path = format!("{}/{day}.txt", data.inputs);
+    let example_path = format!("{}/{day}.txt", data.examples);
+    let module_path = format!("src/bin/{day}.rs");
+
+    let template_name = template.unwrap_or(&config.scaffold.template);
+    let Some(module_template) = templates::find(template_name, &config.scaffold.templates) else {
+        eprintln!(
+            "Unknown template `{template_name}`. Available templates: {}.",
+            templates::available(&config.scaffold.templates).join(", ")
+        );
+        process::exit(1);
+    };
+
+    let files = [
+        (Artifact::Module, module_path.as_str()),
+        (Artifact::Input, input_path.as_str()),
+        (Artifact::Example, example_path.as_str()),
+    ];
+
+    for (artifact, path) in files {
+        let action = Action::new(path, artifact, force);
+        if !dry_run {
+            apply(action, artifact, path, || match artifact {
+                Artifact::Module => templates::render(&module_template, &placeholders(day)),
+                Artifact::Input | Artifact::Example => String::new(),
+            });
         }
+        println!("{}", action.describe(artifact, path, dry_run));
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if !dry_run {
+        println!("---");
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    }
 }
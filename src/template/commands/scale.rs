@@ -0,0 +1,47 @@
+use std::process::{self, Command};
+
+use crate::template::Day;
+
+pub const DEFAULT_SIZE: usize = 16;
+pub const DEFAULT_STEPS: u32 = 8;
+
+/// Benches a part of a day on generated inputs of doubling size and prints its empirical complexity, see
+/// `template::scaling`. Solutions are always built in release mode, like for `time`.
+pub fn handle(day: Day, part: u8, size: usize, steps: u32, seed: Option<u64>) {
+    if size == 0 || steps == 0 {
+        eprintln!("`--size` and `--steps` must be at least 1.");
+        process::exit(1);
+    }
+
+    let seed = seed.unwrap_or_else(super::generate::random_seed);
+
+    let cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--release".to_string(),
+        "--".to_string(),
+        "--scale".to_string(),
+        "--part".to_string(),
+        part.to_string(),
+        "--size".to_string(),
+        size.to_string(),
+        "--steps".to_string(),
+        steps.to_string(),
+        "--seed".to_string(),
+        seed.to_string(),
+    ];
+
+    let status = match Command::new("cargo").args(&cmd_args).status() {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("Failed to scale part {part} of day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
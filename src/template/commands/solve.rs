@@ -1,8 +1,10 @@
+use std::io::{BufRead, BufReader};
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::render::{self, Format};
+use crate::template::{Day, config};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>, verbose: bool) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -18,17 +20,42 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
+    if verbose {
+        cmd_args.push("--verbose".to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
+
+        if let Some(year) = config::get().year {
+            cmd_args.push("--year".to_string());
+            cmd_args.push(year.to_string());
+        }
     }
 
+    let format = render::format();
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
-        .stdout(Stdio::inherit())
+        .env(render::FORMAT_ENV, format.as_str())
+        .stdout(if format == Format::Json {
+            Stdio::piped()
+        } else {
+            Stdio::inherit()
+        })
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
+    // collect the results of the child into a single document.
+    if let Some(stdout) = cmd.stdout.take() {
+        let mut renderer = render::renderer();
+        for line in BufReader::new(stdout).lines() {
+            renderer.output(&line.unwrap());
+        }
+        renderer.summary(&[], None);
+    }
+
     cmd.wait().unwrap();
 }
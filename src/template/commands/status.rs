@@ -0,0 +1,23 @@
+use std::process;
+
+use crate::template::status::{self, DayStatus};
+use crate::template::timings::Timings;
+use crate::template::{all_days, readme_stars, render};
+
+pub fn handle(run_tests: bool, update_readme: bool) {
+    let timings = Timings::read_from_file();
+
+    let statuses: Vec<DayStatus> = all_days()
+        .map(|day| status::collect(day, &timings, run_tests))
+        .collect();
+
+    render::renderer().status(&statuses);
+
+    if update_readme {
+        if let Err(e) = readme_stars::update(&statuses) {
+            eprintln!("Failed to write stars to the readme: {e}");
+            process::exit(1);
+        }
+        eprintln!("Updated stars in the readme.");
+    }
+}
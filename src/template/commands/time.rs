@@ -1,6 +1,8 @@
 use std::collections::HashSet;
 
-use crate::template::run_multi::run_multi;
+use crate::template::environment::Environment;
+use crate::template::render::{self, Format};
+use crate::template::run_multi::{child_commands, run_multi};
 use crate::template::timings::Timings;
 use crate::template::{Day, all_days, readme_benchmarks};
 
@@ -14,23 +16,49 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| !stored_timings.is_day_complete(day))
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // `time` always builds solutions in release mode.
+    let is_release = true;
+    let environment = Environment::detect(child_commands::profile(is_release));
+    if render::format() != Format::Json {
+        println!("Benchmarking on {}.", environment.summary());
+        println!();
+    }
+    for warning in environment.warnings() {
+        eprintln!("Warning: {warning}");
+    }
+
+    let timings = run_multi(&days_to_run, is_release, true)
+        .unwrap()
+        .with_environment(&environment);
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
-        println!();
+        let others = merged_timings.benched_elsewhere(&environment);
+        if !others.is_empty() {
+            let days: Vec<String> = others.iter().map(|t| t.day.to_string()).collect();
+            eprintln!();
+            eprintln!(
+                "Note: the stored timings of day(s) {} were benched on another machine or toolchain. Run `time --all --store` to bench all days on this one.",
+                days.join(", ")
+            );
+        }
+
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
-                println!("Stored updated benchmarks.");
+                // keep stdout a valid JSON document.
+                if render::format() != Format::Json {
+                    println!();
+                    println!("Stored updated benchmarks.");
+                }
             }
             Err(_) => {
                 eprintln!("Failed to store updated benchmarks.");
@@ -0,0 +1,109 @@
+use std::io::{IsTerminal, Write, stderr};
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::template::commands::{download, read, scaffold};
+use crate::template::{Day, aoc_cli};
+
+/// Seconds to wait before each retry of the download. Puzzles are sometimes not served the
+/// second they unlock.
+const RETRY_DELAYS: [u64; 4] = [2, 5, 10, 30];
+
+pub fn handle(wait: bool) {
+    let day = if wait {
+        // fail before waiting, not after.
+        if aoc_cli::check().is_err() {
+            eprintln!(
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            );
+            process::exit(1);
+        }
+        wait_for_unlock()
+    } else {
+        let Some(day) = Day::today() else {
+            eprintln!(
+                "`today` command can only be run between the 1st and \
+                    the last day of the event in december (the 12th from 2025 on). Please use `scaffold` with a specific day, \
+                    or pass `--wait` to wait for the next puzzle."
+            );
+            process::exit(1)
+        };
+        day
+    };
+
+    scaffold::handle(day, &[], false, None);
+    if wait {
+        download_with_retry(day);
+    } else {
+        download::handle(day, &[], false);
+    }
+    read::handle(day, None);
+}
+
+/// Shows a countdown until the next puzzle unlocks and returns its day.
+fn wait_for_unlock() -> Day {
+    let Some((day, unlock)) = Day::next_unlock(Utc::now()) else {
+        eprintln!("Could not determine when the next puzzle unlocks.");
+        process::exit(1);
+    };
+
+    let interactive = stderr().is_terminal();
+    if !interactive {
+        eprintln!("Waiting for day {day} to unlock at {unlock}.");
+    }
+
+    while let Ok(remaining) = (unlock - Utc::now()).to_std() {
+        if interactive {
+            eprint!("\r⏳ Day {day} unlocks in {} ", format_remaining(remaining));
+            let _ = stderr().flush();
+        }
+        // wake up on the second, so the countdown ticks evenly.
+        let subsec = Duration::from_nanos(u64::from(remaining.subsec_nanos()));
+        let step = if subsec.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            subsec
+        };
+        thread::sleep(step.min(remaining));
+    }
+
+    if interactive {
+        eprintln!("\r🎄 Day {day} is unlocked!              ");
+    }
+    day
+}
+
+fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        rest / 3_600,
+        rest % 3_600 / 60,
+        rest % 60
+    );
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+/// Downloads the puzzle, retrying with increasing delays while it is not available yet.
+fn download_with_retry(day: Day) {
+    let mut delays = RETRY_DELAYS.iter();
+    loop {
+        let Err(e) = download::download(day, &[]) else {
+            return;
+        };
+        let Some(delay) = delays.next() else {
+            eprintln!("failed to call aoc-cli: {e}");
+            process::exit(1);
+        };
+        eprintln!("Download of day {day} failed, retrying in {delay}s...");
+        thread::sleep(Duration::from_secs(*delay));
+    }
+}
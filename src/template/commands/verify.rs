@@ -0,0 +1,79 @@
+use std::path::Path;
+use std::{fs, process};
+
+use crate::template::answers::{Answers, INPUT_KINDS};
+use crate::template::render::{self, Format};
+use crate::template::run_multi::{child_commands, get_path_for_bin};
+use crate::template::verification::{self, Verification};
+use crate::template::{Day, all_days, config};
+
+pub fn handle(day: Option<Day>, inputs: &[&'static str], is_release: bool, junit: Option<&str>) {
+    // only verify days that have been scaffolded.
+    let days: Vec<Day> = match day {
+        Some(day) => vec![day],
+        None => all_days()
+            .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+            .collect(),
+    };
+
+    let mut renderer = render::renderer();
+    let mut verifications: Vec<Verification> = vec![];
+
+    for day in days {
+        let answers = match Answers::read(day) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Failed to read answers of day {day}: {e}");
+                process::exit(1);
+            }
+        };
+
+        for input in INPUT_KINDS.iter().filter(|kind| inputs.contains(kind)) {
+            let folder = if *input == "example" {
+                "examples"
+            } else {
+                "inputs"
+            };
+            let input_path = format!("{}/{day}.txt", config::get().data_dir(folder));
+
+            let reports = if Path::new(&input_path).exists() {
+                Some(child_commands::run_reports(day, is_release, input).unwrap())
+            } else {
+                None
+            };
+
+            for part in 1..=2 {
+                let verification = match &reports {
+                    Some(reports) => Verification::new(
+                        day,
+                        part,
+                        input,
+                        reports.iter().find(|r| r.part == part),
+                        answers.get(input, part),
+                    ),
+                    None => Verification::missing_input(day, part, input),
+                };
+
+                renderer.verification(&verification);
+                verifications.push(verification);
+            }
+        }
+    }
+
+    renderer.verification_summary(&verifications);
+
+    if let Some(path) = junit {
+        if let Err(e) = fs::write(path, verification::to_junit(&verifications)) {
+            eprintln!("Failed to write JUnit report: {e}");
+            process::exit(1);
+        }
+
+        if render::format() != Format::Json {
+            println!("Wrote JUnit report to \"{path}\".");
+        }
+    }
+
+    if verifications.iter().any(Verification::is_failure) {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,693 @@
+/// Project configuration, read from `aoc.toml` in the project root.
+///
+/// Every setting is optional. Settings are resolved in the following order, later ones winning:
+///  1. built-in defaults (and the `AOC_YEAR` environment variable for the year, if it is set).
+///  2. the `aoc.toml` file.
+///  3. command-line flags.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::{env, fs, io, process};
+
+use crate::template::readme_benchmarks::MARKER;
+
+pub static CONFIG_FILE_PATH: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Returns the effective configuration of this process.
+/// Loads `aoc.toml` on first access unless [`init`] was called before.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(load)
+}
+
+/// Reads `aoc.toml`, exiting the process if it is invalid.
+pub fn load() -> Config {
+    match Config::read_from_file(CONFIG_FILE_PATH) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to read \"{CONFIG_FILE_PATH}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Sets the effective configuration, e.g. after applying command-line overrides.
+/// Has no effect if the configuration was already accessed.
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// The year of the event, passed to aoc-cli.
+    pub year: Option<u16>,
+    /// Whether `solve` and `all` build solutions in release mode by default.
+    pub release: bool,
+    pub data: DataConfig,
+    pub session: SessionConfig,
+    pub bench: BenchConfig,
+    pub readme: ReadmeConfig,
+    pub submit: SubmitConfig,
+    pub scaffold: ScaffoldConfig,
+    pub seal: SealConfig,
+}
+
+/// Locations of puzzle data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataConfig {
+    /// Directory of all data, e.g. `generated` and `leaderboards`. The other settings default to paths in it.
+    pub root: String,
+    pub inputs: String,
+    pub examples: String,
+    pub puzzles: String,
+    pub answers: String,
+    pub timings: String,
+}
+
+/// Where aoc-cli gets the session token from. Falls back to aoc-cli's own lookup if not set.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SessionConfig {
+    /// Path of a file containing the session cookie.
+    pub file: Option<String>,
+    /// Name of an environment variable containing the session cookie.
+    pub env: Option<String>,
+}
+
+/// Parameters of the benchmark loop in `runner`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchConfig {
+    /// Approximate time spent benching a single part.
+    pub budget_ms: u64,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    pub warmup: u32,
+}
+
+/// Where and how benchmarks are written to the readme.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadmeConfig {
+    pub path: String,
+    pub marker: String,
+    pub heading: String,
+    pub style: TableStyle,
+    /// Path of the SVG chart written next to the table. No chart is written if not set.
+    pub chart: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableStyle {
+    /// Centered columns, days link to their solution.
+    Default,
+    /// Left-aligned columns without links.
+    Minimal,
+}
+
+/// Guards applied before submitting an answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubmitConfig {
+    pub enabled: bool,
+    /// Ask for confirmation before submitting.
+    pub confirm: bool,
+    /// Refuse to submit answers computed by a debug build.
+    pub require_release: bool,
+}
+
+/// Templates used by `scaffold`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaffoldConfig {
+    /// The template used if `--template` is not passed.
+    pub template: String,
+    /// Directory of user templates, e.g. `templates/dp.txt`.
+    pub templates: String,
+}
+
+/// Encrypted archive of inputs, puzzles and answers, see `inputs seal`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SealConfig {
+    pub archive: String,
+    /// File holding the key. Must not be committed.
+    pub key_file: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: env::var("AOC_YEAR").ok().and_then(|x| x.parse().ok()),
+            release: false,
+            data: DataConfig::default(),
+            session: SessionConfig::default(),
+            bench: BenchConfig::default(),
+            readme: ReadmeConfig::default(),
+            submit: SubmitConfig::default(),
+            scaffold: ScaffoldConfig::default(),
+            seal: SealConfig::default(),
+        }
+    }
+}
+
+impl Default for DataConfig {
+    fn default() -> Self {
+        Self::in_root("data")
+    }
+}
+
+impl DataConfig {
+    /// The default locations in the directory `root`.
+    pub fn in_root(root: &str) -> Self {
+        Self {
+            root: root.into(),
+            inputs: format!("{root}/inputs"),
+            examples: format!("{root}/examples"),
+            puzzles: format!("{root}/puzzles"),
+            answers: format!("{root}/answers"),
+            timings: format!("{root}/timings.json"),
+        }
+    }
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget_ms: 1000,
+            min_samples: 10,
+            max_samples: 100_000,
+            warmup: 100,
+        }
+    }
+}
+
+impl Default for ReadmeConfig {
+    fn default() -> Self {
+        Self {
+            path: "README.md".into(),
+            marker: MARKER.into(),
+            heading: "##".into(),
+            style: TableStyle::Default,
+            chart: Some(".assets/benchmarks.svg".into()),
+        }
+    }
+}
+
+impl Default for SubmitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            confirm: false,
+            require_release: false,
+        }
+    }
+}
+
+impl Default for ScaffoldConfig {
+    fn default() -> Self {
+        Self {
+            template: "plain".into(),
+            templates: "templates".into(),
+        }
+    }
+}
+
+impl Default for SealConfig {
+    fn default() -> Self {
+        Self {
+            archive: "data/sealed.zip".into(),
+            key_file: ".aoc-seal-key".into(),
+        }
+    }
+}
+
+impl SessionConfig {
+    /// The session file with a leading `~` replaced by `$HOME`, as written in the sample `aoc.toml`.
+    pub fn file_path(&self) -> Option<String> {
+        self.file.as_deref().map(expand_home)
+    }
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix('~'), env::var("HOME")) {
+        (Some(rest), Ok(home)) if rest.is_empty() || rest.starts_with('/') => {
+            format!("{home}{rest}")
+        }
+        _ => path.to_string(),
+    }
+}
+
+impl Config {
+    /// Reads the configuration from a file. If not present, returns the defaults.
+    pub fn read_from_file(path: &str) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::try_from(s.as_str()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::IO(e)),
+        }
+    }
+
+    /// Returns the directory for a data folder name as used by [`crate::template::read_file`].
+    pub fn data_dir(&self, folder: &str) -> String {
+        match folder {
+            "inputs" => self.data.inputs.clone(),
+            "examples" => self.data.examples.clone(),
+            "puzzles" => self.data.puzzles.clone(),
+            "answers" => self.data.answers.clone(),
+            _ => format!("{}/{folder}", self.data.root),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+    Parser { line: usize, message: String },
+    Invalid(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Parser { line, message } => write!(f, "line {line}: {message}"),
+            Error::Invalid(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A value of the TOML subset supported by the configuration file.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    String(String),
+    Integer(i64),
+    Bool(bool),
+}
+
+impl TryFrom<&str> for Config {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut values = parse_toml(value)?;
+        let mut config = Config::default();
+
+        let mut take = |key: &str| values.remove(key);
+
+        if let Some(v) = take("year") {
+            config.year = Some(as_int("year", v)?);
+        }
+        if let Some(v) = take("release") {
+            config.release = as_bool("release", v)?;
+        }
+
+        if let Some(v) = take("data.root") {
+            config.data = DataConfig::in_root(&as_string("data.root", v)?);
+        }
+        if let Some(v) = take("data.inputs") {
+            config.data.inputs = as_string("data.inputs", v)?;
+        }
+        if let Some(v) = take("data.examples") {
+            config.data.examples = as_string("data.examples", v)?;
+        }
+        if let Some(v) = take("data.puzzles") {
+            config.data.puzzles = as_string("data.puzzles", v)?;
+        }
+        if let Some(v) = take("data.answers") {
+            config.data.answers = as_string("data.answers", v)?;
+        }
+        if let Some(v) = take("data.timings") {
+            config.data.timings = as_string("data.timings", v)?;
+        }
+
+        if let Some(v) = take("session.file") {
+            config.session.file = Some(as_string("session.file", v)?);
+        }
+        if let Some(v) = take("session.env") {
+            config.session.env = Some(as_string("session.env", v)?);
+        }
+
+        if let Some(v) = take("bench.budget_ms") {
+            config.bench.budget_ms = as_int("bench.budget_ms", v)?;
+        }
+        if let Some(v) = take("bench.min_samples") {
+            config.bench.min_samples = as_int("bench.min_samples", v)?;
+        }
+        if let Some(v) = take("bench.max_samples") {
+            config.bench.max_samples = as_int("bench.max_samples", v)?;
+        }
+        if let Some(v) = take("bench.warmup") {
+            config.bench.warmup = as_int("bench.warmup", v)?;
+        }
+
+        if let Some(v) = take("readme.path") {
+            config.readme.path = as_string("readme.path", v)?;
+        }
+        if let Some(v) = take("readme.marker") {
+            config.readme.marker = as_string("readme.marker", v)?;
+        }
+        if let Some(v) = take("readme.heading") {
+            config.readme.heading = as_string("readme.heading", v)?;
+        }
+        if let Some(v) = take("readme.style") {
+            config.readme.style = match as_string("readme.style", v)?.as_str() {
+                "default" => TableStyle::Default,
+                "minimal" => TableStyle::Minimal,
+                s => {
+                    return Err(Error::Invalid(format!(
+                        "`readme.style` must be \"default\" or \"minimal\", got \"{s}\"."
+                    )));
+                }
+            };
+        }
+
+        if let Some(v) = take("readme.chart") {
+            // an empty path disables the chart.
+            let chart = as_string("readme.chart", v)?;
+            config.readme.chart = Some(chart).filter(|s| !s.is_empty());
+        }
+
+        if let Some(v) = take("submit.enabled") {
+            config.submit.enabled = as_bool("submit.enabled", v)?;
+        }
+        if let Some(v) = take("submit.confirm") {
+            config.submit.confirm = as_bool("submit.confirm", v)?;
+        }
+        if let Some(v) = take("submit.require_release") {
+            config.submit.require_release = as_bool("submit.require_release", v)?;
+        }
+
+        if let Some(v) = take("scaffold.template") {
+            config.scaffold.template = as_string("scaffold.template", v)?;
+        }
+        if let Some(v) = take("scaffold.templates") {
+            config.scaffold.templates = as_string("scaffold.templates", v)?;
+        }
+
+        if let Some(v) = take("seal.archive") {
+            config.seal.archive = as_string("seal.archive", v)?;
+        }
+        if let Some(v) = take("seal.key_file") {
+            config.seal.key_file = as_string("seal.key_file", v)?;
+        }
+
+        if config.bench.min_samples > config.bench.max_samples {
+            return Err(Error::Invalid(
+                "`bench.min_samples` must not be greater than `bench.max_samples`.".into(),
+            ));
+        }
+
+        let mut unknown: Vec<_> = values.into_keys().collect();
+        unknown.sort_unstable();
+        if let Some(key) = unknown.first() {
+            return Err(Error::Invalid(format!("unknown setting `{key}`.")));
+        }
+
+        Ok(config)
+    }
+}
+
+fn as_string(key: &str, value: Value) -> Result<String, Error> {
+    match value {
+        Value::String(s) => Ok(s),
+        _ => Err(Error::Invalid(format!("`{key}` must be a string."))),
+    }
+}
+
+fn as_bool(key: &str, value: Value) -> Result<bool, Error> {
+    match value {
+        Value::Bool(b) => Ok(b),
+        _ => Err(Error::Invalid(format!("`{key}` must be a boolean."))),
+    }
+}
+
+fn as_int<T: TryFrom<i64>>(key: &str, value: Value) -> Result<T, Error> {
+    match value {
+        Value::Integer(i) => {
+            T::try_from(i).map_err(|_| Error::Invalid(format!("`{key}` is out of range.")))
+        }
+        _ => Err(Error::Invalid(format!("`{key}` must be an integer."))),
+    }
+}
+
+/// Parses the subset of TOML used by the configuration file: `[section]` headers and
+/// `key = value` pairs with string, integer and boolean values. Keys are flattened to `section.key`.
+pub(crate) fn parse_toml(s: &str) -> Result<HashMap<String, Value>, Error> {
+    let mut values = HashMap::new();
+    let mut section = String::new();
+
+    for (i, line) in s.lines().enumerate() {
+        let line_number = i + 1;
+        let err = |message: &str| Error::Parser {
+            line: line_number,
+            message: message.into(),
+        };
+
+        let line = strip_comment(line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.strip_suffix(']').ok_or_else(|| err("expected `]`."))?;
+            section = name.trim().to_string();
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| err("expected `key = value`."))?;
+
+        let key = key.trim();
+        let key = if section.is_empty() {
+            key.to_string()
+        } else {
+            format!("{section}.{key}")
+        };
+
+        let value = parse_value(value.trim()).ok_or_else(|| err("invalid value."))?;
+
+        if values.insert(key.clone(), value).is_some() {
+            return Err(err(&format!("duplicate key `{key}`.")));
+        }
+    }
+
+    Ok(values)
+}
+
+/// Removes a trailing `# comment`, ignoring `#` inside of strings.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut chars = line.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            // skip escaped characters.
+            '\\' if in_string => {
+                chars.next();
+            }
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+fn parse_value(s: &str) -> Option<Value> {
+    match s {
+        "true" => return Some(Value::Bool(true)),
+        "false" => return Some(Value::Bool(false)),
+        _ => {}
+    }
+
+    if let Some(s) = s.strip_prefix('"') {
+        let s = s.strip_suffix('"')?;
+        let mut out = String::with_capacity(s.len());
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c == '\\' {
+                out.push(match chars.next()? {
+                    'n' => '\n',
+                    't' => '\t',
+                    c @ ('"' | '\\') => c,
+                    _ => return None,
+                });
+            } else {
+                out.push(c);
+            }
+        }
+        return Some(Value::String(out));
+    }
+
+    s.replace('_', "").parse().ok().map(Value::Integer)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Writes the configuration back in the same format it is read from.
+impl Display for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let string = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let optional = |key: &str, s: &Option<String>| match s {
+            Some(s) => format!("{key} = {}", string(s)),
+            None => format!("# {key} is not set"),
+        };
+
+        match self.year {
+            Some(year) => writeln!(f, "year = {year}")?,
+            None => writeln!(f, "# year is not set")?,
+        }
+        writeln!(f, "release = {}", self.release)?;
+
+        writeln!(f, "\n[data]")?;
+        writeln!(f, "root = {}", string(&self.data.root))?;
+        writeln!(f, "inputs = {}", string(&self.data.inputs))?;
+        writeln!(f, "examples = {}", string(&self.data.examples))?;
+        writeln!(f, "puzzles = {}", string(&self.data.puzzles))?;
+        writeln!(f, "answers = {}", string(&self.data.answers))?;
+        writeln!(f, "timings = {}", string(&self.data.timings))?;
+
+        writeln!(f, "\n[session]")?;
+        writeln!(f, "{}", optional("file", &self.session.file))?;
+        writeln!(f, "{}", optional("env", &self.session.env))?;
+
+        writeln!(f, "\n[bench]")?;
+        writeln!(f, "budget_ms = {}", self.bench.budget_ms)?;
+        writeln!(f, "min_samples = {}", self.bench.min_samples)?;
+        writeln!(f, "max_samples = {}", self.bench.max_samples)?;
+        writeln!(f, "warmup = {}", self.bench.warmup)?;
+
+        writeln!(f, "\n[readme]")?;
+        writeln!(f, "path = {}", string(&self.readme.path))?;
+        writeln!(f, "marker = {}", string(&self.readme.marker))?;
+        writeln!(f, "heading = {}", string(&self.readme.heading))?;
+        let style = match self.readme.style {
+            TableStyle::Default => "default",
+            TableStyle::Minimal => "minimal",
+        };
+        writeln!(f, "style = {}", string(style))?;
+        writeln!(
+            f,
+            "chart = {}",
+            string(self.readme.chart.as_deref().unwrap_or_default())
+        )?;
+
+        writeln!(f, "\n[submit]")?;
+        writeln!(f, "enabled = {}", self.submit.enabled)?;
+        writeln!(f, "confirm = {}", self.submit.confirm)?;
+        writeln!(f, "require_release = {}", self.submit.require_release)?;
+
+        writeln!(f, "\n[scaffold]")?;
+        writeln!(f, "template = {}", string(&self.scaffold.template))?;
+        writeln!(f, "templates = {}", string(&self.scaffold.templates))?;
+
+        writeln!(f, "\n[seal]")?;
+        writeln!(f, "archive = {}", string(&self.seal.archive))?;
+        write!(f, "key_file = {}", string(&self.seal.key_file))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Config, TableStyle, expand_home};
+    use std::env;
+
+    #[test]
+    fn handles_empty_config() {
+        let config = Config::try_from("").unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn handles_config() {
+        let config = Config::try_from(
+            r#"
+            # top-level settings
+            year = 2024
+            release = true
+
+            [data]
+            inputs = "inputs" # inline comment
+
+            [bench]
+            budget_ms = 2_000
+
+            [readme]
+            marker = "<!-- # -->"
+            style = "minimal"
+
+            [submit]
+            confirm = true
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2024));
+        assert!(config.release);
+        assert_eq!(config.data.inputs, "inputs");
+        assert_eq!(config.data.examples, "data/examples");
+        assert_eq!(config.bench.budget_ms, 2000);
+        assert_eq!(config.readme.marker, "<!-- # -->");
+        assert_eq!(config.readme.style, TableStyle::Minimal);
+        assert!(config.submit.confirm);
+        assert!(config.submit.enabled);
+    }
+
+    #[test]
+    fn moves_data_into_root() {
+        let config = Config::try_from("[data]\nroot = \"aoc\"\nanswers = \"answers\"").unwrap();
+        assert_eq!(config.data.inputs, "aoc/inputs");
+        assert_eq!(config.data.answers, "answers");
+        assert_eq!(config.data.timings, "aoc/timings.json");
+        assert_eq!(config.data_dir("examples"), "aoc/examples");
+        assert_eq!(config.data_dir("generated"), "aoc/generated");
+    }
+
+    #[test]
+    fn roundtrips_display() {
+        let mut config = Config::default();
+        config.year = Some(2023);
+        config.session.file = Some("~/.aoc \"session\"".into());
+        config.readme.style = TableStyle::Minimal;
+        config.readme.chart = None;
+        config.scaffold.template = "grid".into();
+        config.seal.key_file = "~/.aoc-seal-key".into();
+
+        let parsed = Config::try_from(config.to_string().as_str()).unwrap();
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn expands_home_in_session_file() {
+        let home = env::var("HOME").unwrap();
+        assert_eq!(
+            expand_home("~/.aoc-session"),
+            format!("{home}/.aoc-session")
+        );
+        assert_eq!(expand_home("~"), home);
+        assert_eq!(expand_home("~user/.aoc-session"), "~user/.aoc-session");
+        assert_eq!(expand_home("session/~"), "session/~");
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_unknown_keys() {
+        Config::try_from("[bench]\nbudget = 10").unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_wrong_types() {
+        Config::try_from("release = \"yes\"").unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_lines() {
+        Config::try_from("[data\ninputs = \"x\"").unwrap();
+    }
+}
@@ -0,0 +1,149 @@
+/// Information about the current run, for parts that take a second argument `ctx: &Context`:
+/// ```ignore
+/// pub fn part_one(input: &str, ctx: &Context) -> Option<u64> {
+///     advent_of_code::debug!(ctx, "parsed {} lines", input.lines().count());
+///     ctx.record("lines", input.lines().count());
+///     // ...
+/// }
+/// ```
+use std::env;
+use std::fmt::{Arguments, Display};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::template::params::Params;
+
+/// The kind of input a part runs against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputKind {
+    /// The personal puzzle input in `data/inputs`.
+    Real,
+    /// An example in `data/examples`.
+    Example,
+}
+
+impl InputKind {
+    /// The kind of input in a data folder, i.e. `inputs` or `examples`.
+    pub fn from_folder(folder: &str) -> Self {
+        match folder {
+            "examples" => Self::Example,
+            _ => Self::Real,
+        }
+    }
+}
+
+/// Passed to parts by the runner. Parts may run on multiple threads, so the context is `Sync`.
+#[derive(Debug)]
+pub struct Context {
+    pub kind: InputKind,
+    pub params: Params,
+    /// How often `--verbose` was passed.
+    verbosity: u8,
+    /// Set while the part is benched, which silences debug output and ignores recorded values.
+    benching: AtomicBool,
+    records: Mutex<Vec<(String, String)>>,
+}
+
+impl Context {
+    pub fn new(kind: InputKind, params: Params) -> Self {
+        let verbosity = env::args()
+            .filter(|arg| arg == "--verbose" || arg == "-v")
+            .count();
+
+        Self {
+            kind,
+            params,
+            verbosity: u8::try_from(verbosity).unwrap_or(u8::MAX),
+            benching: AtomicBool::new(false),
+            records: Mutex::new(vec![]),
+        }
+    }
+
+    pub fn is_example(&self) -> bool {
+        self.kind == InputKind::Example
+    }
+
+    /// Whether debug output of a verbosity level is shown. Always false while benching.
+    pub fn is_verbose(&self, level: u8) -> bool {
+        self.verbosity >= level && !self.benching.load(Ordering::Relaxed)
+    }
+
+    /// Prints a line of debug output to stderr if `--verbose` is set. Prefer [`crate::debug!`],
+    /// which does not format its arguments when the output is not shown.
+    pub fn debug(&self, args: Arguments) {
+        if self.is_verbose(1) {
+            eprintln!("{args}");
+        }
+    }
+
+    /// Records an intermediate value, which is shown below the answer of the part.
+    /// Recording a name again replaces its value. Values recorded while benching are ignored.
+    pub fn record(&self, name: &str, value: impl Display) {
+        if self.benching.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let mut records = self.records.lock().unwrap();
+        let value = value.to_string();
+        match records.iter_mut().find(|(n, _)| n == name) {
+            Some(record) => record.1 = value,
+            None => records.push((name.to_string(), value)),
+        }
+    }
+
+    /// Returns and clears the recorded values.
+    pub fn take_records(&self) -> Vec<(String, String)> {
+        std::mem::take(&mut *self.records.lock().unwrap())
+    }
+
+    pub(crate) fn set_benching(&self, benching: bool) {
+        self.benching.store(benching, Ordering::Relaxed);
+    }
+}
+
+/// Prints a line of debug output if `--verbose` is passed to `solve`. The arguments are not evaluated otherwise.
+/// ```ignore
+/// advent_of_code::debug!(ctx, "{lights:016b}");
+/// ```
+#[macro_export]
+macro_rules! debug {
+    ($ctx:expr, $($arg:tt)*) => {
+        if $ctx.is_verbose(1) {
+            $ctx.debug(format_args!($($arg)*));
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Context, InputKind};
+    use crate::template::params::Params;
+
+    #[test]
+    fn records_values() {
+        let ctx = Context::new(InputKind::Example, Params::default());
+        ctx.record("nodes", 5);
+        ctx.record("edges", 7);
+        ctx.record("nodes", 6);
+
+        assert_eq!(
+            ctx.take_records(),
+            vec![("nodes".into(), "6".into()), ("edges".into(), "7".into())]
+        );
+        assert!(ctx.take_records().is_empty());
+    }
+
+    #[test]
+    fn ignores_records_while_benching() {
+        let ctx = Context::new(InputKind::Real, Params::default());
+        ctx.set_benching(true);
+        ctx.record("nodes", 5);
+        assert!(!ctx.is_verbose(0));
+
+        ctx.set_benching(false);
+        assert!(ctx.is_verbose(0));
+        assert!(ctx.take_records().is_empty());
+    }
+}
@@ -3,11 +3,17 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
+/// The last day of the event of a year: 25 puzzles until 2024, 12 from 2025 on.
+#[cfg(feature = "today")]
+fn last_day(year: i32) -> u32 {
+    if year >= 2025 { 12 } else { 25 }
+}
+
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
 /// # Display
@@ -39,16 +45,38 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's between the 1st and the last day of the event in december,
+    /// `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        if today.month() == 12 && today.day() <= last_day(today.year()) {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
         }
     }
+
+    /// Returns the next day to unlock after `now`, and when it unlocks: midnight server time
+    /// on one of the 1st to the last day of the event in december.
+    pub fn next_unlock(now: DateTime<Utc>) -> Option<(Self, DateTime<Utc>)> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let tomorrow = now.with_timezone(&offset).date_naive().succ_opt()?;
+
+        let date = match (tomorrow.month(), tomorrow.day()) {
+            (12, day) if day <= last_day(tomorrow.year()) => tomorrow,
+            (12, _) => NaiveDate::from_ymd_opt(tomorrow.year() + 1, 12, 1)?,
+            _ => NaiveDate::from_ymd_opt(tomorrow.year(), 12, 1)?,
+        };
+
+        let unlock = date
+            .and_hms_opt(0, 0, 0)?
+            .and_local_timezone(offset)
+            .single()?
+            .with_timezone(&Utc);
+
+        Some((Self::new(u8::try_from(date.day()).ok()?)?, unlock))
+    }
 }
 
 impl Display for Day {
@@ -176,6 +204,42 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[cfg(feature = "today")]
+    #[test]
+    fn next_unlock() {
+        use chrono::{DateTime, Utc};
+
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+
+        // a minute before midnight EST.
+        assert_eq!(
+            Day::next_unlock(at("2024-12-05T04:59:00Z")),
+            Some((Day(5), at("2024-12-05T05:00:00Z")))
+        );
+        assert_eq!(
+            Day::next_unlock(at("2024-12-05T05:00:00Z")),
+            Some((Day(6), at("2024-12-06T05:00:00Z")))
+        );
+        assert_eq!(
+            Day::next_unlock(at("2024-11-20T12:00:00Z")),
+            Some((Day(1), at("2024-12-01T05:00:00Z")))
+        );
+        assert_eq!(
+            Day::next_unlock(at("2024-12-25T12:00:00Z")),
+            Some((Day(1), at("2025-12-01T05:00:00Z")))
+        );
+
+        // from 2025 on, the event ends with the 12th.
+        assert_eq!(
+            Day::next_unlock(at("2025-12-11T05:00:00Z")),
+            Some((Day(12), at("2025-12-12T05:00:00Z")))
+        );
+        assert_eq!(
+            Day::next_unlock(at("2025-12-12T05:00:00Z")),
+            Some((Day(1), at("2026-12-01T05:00:00Z")))
+        );
+    }
 }
 
 /* -------------------------------------------------------------------------- */
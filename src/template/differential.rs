@@ -0,0 +1,530 @@
+/// Differential testing: runs the variants of a part on many inputs and reports the first input they
+/// disagree on, shrunk to a small reproducer.
+///
+/// A day registers the variants of its parts with `solution!`. The first variant of a part is the reference,
+/// `part_one` and `part_two` are compared with it as well:
+/// ```ignore
+/// advent_of_code::solution!(9, generate = generate, variants = {
+///     1: [part_one_brute],
+///     2: [part_two_brute],
+/// });
+/// ```
+/// `diff <day>` feeds the variants inputs of the day's generator, or mutated examples and inputs if it has none.
+/// Inputs that violate the assumptions of the day are skipped, see [`crate::template::assumptions`].
+///
+/// Panics of the variants are caught and reported as answers. This needs a profile that unwinds: `diff` builds
+/// the `diff` profile instead of `release`, which aborts on panics.
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::{env, fs, process, slice};
+
+use crate::template::assumptions::{Assumption, violations};
+use crate::template::context::Context;
+use crate::template::generate::{Generator, Rng};
+use crate::template::runner::{Outcome, PartResult, panic_message};
+use crate::template::{Day, config, try_read_file};
+
+/// What a variant returned for an input.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Solved(String),
+    NotImplemented,
+    Failed(String),
+    Panicked(String),
+}
+
+impl Answer {
+    pub fn of<R: PartResult>(result: R) -> Self {
+        match result.into_outcome() {
+            Outcome::Solved(answer) => Self::Solved(answer.to_string()),
+            Outcome::NotImplemented => Self::NotImplemented,
+            Outcome::Failed(e) => Self::Failed(e.to_string()),
+        }
+    }
+}
+
+/// Failures and panics are equal regardless of their message.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Solved(a), Self::Solved(b)) => a == b,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Solved(answer) => write!(f, "{answer}"),
+            Self::NotImplemented => write!(f, "not implemented"),
+            Self::Failed(e) => write!(f, "failed: {e}"),
+            Self::Panicked(message) => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+type VariantFn = dyn Fn(&str, &Context) -> Answer;
+
+/// An implementation of a part, built by `solution!`.
+pub struct Variant {
+    pub name: &'static str,
+    pub part: u8,
+    func: Box<VariantFn>,
+}
+
+impl Variant {
+    pub fn new(
+        name: &'static str,
+        part: u8,
+        func: impl Fn(&str, &Context) -> Answer + 'static,
+    ) -> Self {
+        Self {
+            name,
+            part,
+            func: Box::new(func),
+        }
+    }
+
+    /// Runs the variant, catching panics.
+    pub fn answer(&self, input: &str, ctx: &Context) -> Answer {
+        panic::catch_unwind(AssertUnwindSafe(|| (self.func)(input, ctx)))
+            .unwrap_or_else(|payload| Answer::Panicked(panic_message(payload.as_ref())))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The arguments `diff` passes to a solution: `--differential --runs <runs> --size <size> --seed <seed> [--mutate]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Request {
+    pub runs: u64,
+    pub size: usize,
+    pub seed: u64,
+    /// Mutate the example and the input instead of using the generator.
+    pub mutate: bool,
+}
+
+/// Returns the request if the solution was started by `diff`.
+pub fn request() -> Option<Request> {
+    let args: Vec<String> = env::args().collect();
+    if !args.iter().any(|arg| arg == "--differential") {
+        return None;
+    }
+
+    let value = |name: &str| {
+        let i = args.iter().position(|arg| arg == name)?;
+        args.get(i + 1)
+    };
+
+    let runs = value("--runs").and_then(|s| s.parse().ok());
+    let size = value("--size").and_then(|s| s.parse().ok());
+    let seed = value("--seed").and_then(|s| s.parse().ok());
+
+    match (runs, size, seed) {
+        (Some(runs), Some(size), Some(seed)) => Some(Request {
+            runs,
+            size,
+            seed,
+            mutate: args.iter().any(|arg| arg == "--mutate"),
+        }),
+        _ => {
+            eprintln!(
+                "`--differential` requires `--runs <runs>`, `--size <size>` and `--seed <seed>`."
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Variants that answered differently than the reference, the first variant of the part.
+struct Disagreement {
+    part: u8,
+    answers: Vec<(&'static str, Answer)>,
+}
+
+impl Disagreement {
+    fn differs(&self, name: &str) -> bool {
+        self.answers
+            .iter()
+            .any(|(n, answer)| *n == name && *answer != self.answers[0].1)
+    }
+}
+
+/// Runs the variants of each part that has more than one. Inputs the reference cannot solve are skipped,
+/// as they are most likely invalid. Variants that are not implemented are ignored.
+fn compare(groups: &[Vec<&Variant>], input: &str, ctx: &Context) -> Option<Disagreement> {
+    for group in groups {
+        let reference = group[0].answer(input, ctx);
+        if !matches!(reference, Answer::Solved(_)) {
+            continue;
+        }
+
+        let mut answers = vec![(group[0].name, reference.clone())];
+        let mut disagree = false;
+        for variant in &group[1..] {
+            let answer = variant.answer(input, ctx);
+            disagree |= answer != Answer::NotImplemented && answer != reference;
+            answers.push((variant.name, answer));
+        }
+
+        if disagree {
+            return Some(Disagreement {
+                part: group[0].part,
+                answers,
+            });
+        }
+    }
+    None
+}
+
+/// Where an input of a run comes from, to tell how to reproduce it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Source {
+    Generated { size: usize, seed: u64 },
+    Mutated { seed: u64 },
+}
+
+/// Compares the variants on `request.runs` inputs and exits. Called by `solution!` instead of running the parts.
+pub fn run(
+    day: Day,
+    request: Request,
+    variants: Vec<Variant>,
+    generator: Option<&dyn Generator>,
+    assumptions: &[Assumption],
+    ctx: &Context,
+) -> ! {
+    let mut groups: Vec<Vec<&Variant>> = vec![];
+    for part in [1, 2] {
+        let group: Vec<&Variant> = variants.iter().filter(|v| v.part == part).collect();
+        if group.len() > 1 {
+            groups.push(group);
+        }
+    }
+
+    if groups.is_empty() {
+        eprintln!(
+            "Day {day} has no variants to compare. Register them with `solution!({}, variants = {{ 1: [part_one_brute] }})`.",
+            day.into_inner()
+        );
+        process::exit(1);
+    }
+
+    let generator = generator.filter(|_| !request.mutate);
+    let bases: Vec<String> = ["examples", "inputs"]
+        .iter()
+        .filter_map(|folder| try_read_file(folder, day).ok())
+        .filter(|input| !input.trim().is_empty())
+        .collect();
+
+    if generator.is_none() && bases.is_empty() {
+        eprintln!(
+            "Day {day} has no generator, example or input to mutate. Register a generator with `solution!({}, generate = generate)`.",
+            day.into_inner()
+        );
+        process::exit(1);
+    }
+
+    // panics are expected and reported with the answers.
+    panic::set_hook(Box::new(|_| {}));
+
+    // the solutions rely on the assumptions, so they may disagree on inputs that violate them for no reason.
+    let assumed = |input: &str| violations(assumptions, input, ctx).is_empty();
+    let mut skipped = 0;
+
+    for run in 0..request.runs {
+        let seed = request.seed.wrapping_add(run);
+        // sizes grow over the runs, so that disagreements are found on small inputs first.
+        let size = (request.size * (run as usize + 1)).div_ceil(request.runs as usize);
+
+        let (input, source) = match generator {
+            Some(generator) => (
+                generator.generate(size, &mut Rng::new(seed)),
+                Source::Generated { size, seed },
+            ),
+            None => {
+                let mut rng = Rng::new(seed);
+                let base = rng.pick(&bases).clone();
+                (mutate(&base, &mut rng), Source::Mutated { seed })
+            }
+        };
+
+        if !assumed(&input) {
+            skipped += 1;
+            continue;
+        }
+
+        let Some(disagreement) = compare(&groups, &input, ctx) else {
+            continue;
+        };
+
+        let group = groups
+            .iter()
+            .find(|g| g[0].part == disagreement.part)
+            .unwrap();
+        let reproduces = |candidate: &str| {
+            assumed(candidate)
+                && compare(slice::from_ref(group), candidate, ctx).is_some_and(|d| {
+                    d.answers
+                        .iter()
+                        .any(|(name, _)| disagreement.differs(name) && d.differs(name))
+                })
+        };
+
+        let (shrunk, source) = shrink(&input, &source, generator, &reproduces);
+        let disagreement = compare(slice::from_ref(group), &shrunk, ctx).unwrap();
+        report(day, run, &source, &input, &shrunk, &disagreement);
+    }
+
+    let what = match generator {
+        Some(_) => format!(
+            "generated inputs of size up to {} (seeds {} to {})",
+            request.size,
+            request.seed,
+            request.seed + request.runs - 1
+        ),
+        None => format!(
+            "mutated inputs (seeds {} to {})",
+            request.seed,
+            request.seed + request.runs - 1
+        ),
+    };
+    if skipped == request.runs {
+        eprintln!(
+            "All {skipped} {what} violate the assumptions of day {day}, nothing was compared."
+        );
+        process::exit(1);
+    }
+    if skipped > 0 {
+        println!(
+            "Skipped {skipped} input(s) that violate the assumptions of day {day}, see `check {day}`."
+        );
+    }
+    println!("All variants agree on {} {what}.", request.runs - skipped);
+    process::exit(0);
+}
+
+/// Randomly deletes, duplicates and swaps lines, and changes digits.
+fn mutate(input: &str, rng: &mut Rng) -> String {
+    let mut lines: Vec<String> = input.lines().map(String::from).collect();
+
+    for _ in 0..rng.range(1..4) {
+        if lines.is_empty() {
+            break;
+        }
+        let i = rng.below(lines.len() as u64) as usize;
+        match rng.below(4) {
+            0 => {
+                lines.remove(i);
+            }
+            1 => lines.insert(i, lines[i].clone()),
+            2 => {
+                let j = rng.below(lines.len() as u64) as usize;
+                lines.swap(i, j);
+            }
+            _ => {
+                let digits: Vec<usize> = lines[i]
+                    .char_indices()
+                    .filter(|(_, c)| c.is_ascii_digit())
+                    .map(|(j, _)| j)
+                    .collect();
+                if !digits.is_empty() {
+                    let j = *rng.pick(&digits);
+                    let digit = char::from(b'0' + rng.below(10) as u8);
+                    lines[i].replace_range(j..j + 1, &digit.to_string());
+                }
+            }
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Shrinks an input while it `reproduces` the disagreement. Generated inputs are generated again with smaller
+/// sizes and the same seed, so that they stay valid. Mutated inputs lose chunks of lines.
+fn shrink(
+    input: &str,
+    source: &Source,
+    generator: Option<&dyn Generator>,
+    reproduces: &dyn Fn(&str) -> bool,
+) -> (String, Source) {
+    if let (Some(generator), Source::Generated { size, seed }) = (generator, *source) {
+        return (1..size)
+            .map(|size| (generator.generate(size, &mut Rng::new(seed)), size))
+            .find(|(candidate, _)| reproduces(candidate))
+            .map_or((input.to_string(), *source), |(candidate, size)| {
+                (candidate, Source::Generated { size, seed })
+            });
+    }
+
+    let mut lines: Vec<&str> = input.lines().collect();
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        let mut removed = false;
+        let mut i = 0;
+        while i < lines.len() {
+            let mut candidate = lines.clone();
+            candidate.drain(i..(i + chunk).min(lines.len()));
+            if !candidate.is_empty() && reproduces(&(candidate.join("\n") + "\n")) {
+                lines = candidate;
+                removed = true;
+            } else {
+                i += chunk;
+            }
+        }
+        if !removed {
+            chunk /= 2;
+        }
+    }
+
+    (lines.join("\n") + "\n", *source)
+}
+
+/// Saves the shrunk input as an example file, with the answer of the reference as the expected answer.
+fn save(day: Day, part: u8, name: &str, input: &str, expected: &str) -> Result<PathBuf, String> {
+    let path = Path::new(&config::get().data_dir("examples"))
+        .join(day.to_string())
+        .join(format!("{name}.txt"));
+
+    let expected = if expected.parse::<i64>().is_ok() {
+        expected.to_string()
+    } else {
+        format!("{expected:?}")
+    };
+    let text = format!("---\npart_{part} = {expected}\n---\n{input}");
+
+    fs::create_dir_all(path.parent().unwrap())
+        .and_then(|()| fs::write(&path, text))
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    Ok(path)
+}
+
+fn report(
+    day: Day,
+    run: u64,
+    source: &Source,
+    input: &str,
+    shrunk: &str,
+    disagreement: &Disagreement,
+) -> ! {
+    let part = disagreement.part;
+    let (seed, reproduce) = match source {
+        Source::Generated { size, seed } => {
+            (seed, format!("`gen {day} --size {size} --seed {seed}`"))
+        }
+        Source::Mutated { seed } => (seed, format!("`diff {day} --mutate --seed {seed}`")),
+    };
+
+    println!(
+        "Day {day}, part {part}: variants disagree on input {}, shrunk from {} to {} line(s) ({reproduce}):",
+        run + 1,
+        input.lines().count(),
+        shrunk.lines().count()
+    );
+    println!();
+
+    let width = disagreement
+        .answers
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let answer_width = disagreement
+        .answers
+        .iter()
+        .map(|(_, answer)| answer.to_string().chars().count())
+        .max()
+        .unwrap_or(0);
+    for (i, (name, answer)) in disagreement.answers.iter().enumerate() {
+        let note = if i == 0 {
+            "  (reference)"
+        } else if disagreement.differs(name) {
+            "  ✗"
+        } else {
+            ""
+        };
+        let line = format!(
+            "  {name:width$}  {:answer_width$}{note}",
+            answer.to_string()
+        );
+        println!("{}", line.trim_end());
+    }
+    println!();
+
+    let reference = &disagreement.answers[0];
+    let Answer::Solved(expected) = &reference.1 else {
+        unreachable!("disagreements always have a solved reference")
+    };
+    match save(day, part, &format!("diff-{part}-{seed}"), shrunk, expected) {
+        Ok(path) => println!(
+            "Saved the input to {}, expecting the answer of `{}`. `example_tests!` turns it into a test.",
+            path.display(),
+            reference.0
+        ),
+        Err(e) => eprintln!("{e}"),
+    }
+    process::exit(1);
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, Source, mutate, shrink};
+    use crate::template::generate::Rng;
+
+    #[test]
+    fn compares_answers() {
+        assert_eq!(Answer::Solved("1".into()), Answer::Solved("1".into()));
+        assert_ne!(Answer::Solved("1".into()), Answer::Solved("2".into()));
+        assert_eq!(
+            Answer::Panicked("index out of bounds".into()),
+            Answer::Panicked("attempt to subtract with overflow".into())
+        );
+        assert_ne!(Answer::Failed("a".into()), Answer::Panicked("a".into()));
+    }
+
+    #[test]
+    fn shrinks_inputs() {
+        let input: String = (1..=100).map(|n| format!("{n}\n")).collect();
+        // disagrees whenever both 17 and 42 are in the input.
+        let reproduces = |candidate: &str| {
+            let lines: Vec<&str> = candidate.lines().collect();
+            lines.contains(&"17") && lines.contains(&"42")
+        };
+
+        let source = Source::Mutated { seed: 0 };
+        let shrunk = shrink(&input, &source, None, &reproduces);
+        assert_eq!(shrunk, ("17\n42\n".to_string(), source));
+    }
+
+    #[test]
+    fn shrinks_generated_inputs_by_size() {
+        let generate =
+            |size: usize, _: &mut Rng| -> String { (0..size).map(|n| format!("{n}\n")).collect() };
+        let reproduces =
+            |candidate: &str| candidate.lines().count() >= 3 && candidate.contains("2\n");
+
+        let input = generate(50, &mut Rng::new(0));
+        let source = Source::Generated { size: 50, seed: 0 };
+        let shrunk = shrink(&input, &source, Some(&generate), &reproduces);
+        assert_eq!(
+            shrunk,
+            (
+                "0\n1\n2\n".to_string(),
+                Source::Generated { size: 3, seed: 0 }
+            )
+        );
+    }
+
+    #[test]
+    fn mutates_inputs() {
+        let input = "1,2\n3,4\n5,6\n";
+        let mutated: Vec<String> = (0..20)
+            .map(|seed| mutate(input, &mut Rng::new(seed)))
+            .collect();
+        assert!(mutated.iter().any(|m| m != input));
+        assert!(mutated.iter().all(|m| m.ends_with('\n')));
+    }
+}
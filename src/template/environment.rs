@@ -0,0 +1,373 @@
+/// Describes the machine and the toolchain that timings were benched with, so that timings of different
+/// machines in `timings.json` can be told apart.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::process::Command;
+use std::{env, fs, thread};
+
+use tinyjson::JsonValue;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Environment {
+    /// The CPU model, e.g. `AMD Ryzen 7 5800X 8-Core Processor`.
+    pub cpu: String,
+    /// The number of threads that can run in parallel, i.e. logical CPUs allowed by the affinity mask, not physical
+    /// cores.
+    pub threads: usize,
+    /// The CPU frequency governor, e.g. `performance`. Only known on Linux.
+    pub governor: Option<String>,
+    /// The kernel name and release, e.g. `Linux 6.8.0`.
+    pub kernel: String,
+    /// The output of `rustc --version`.
+    pub rustc: String,
+    /// The `target-cpu` of the rustflags, e.g. `native`.
+    pub target_cpu: Option<String>,
+    /// The target features enabled by the rustflags, e.g. `avx2`.
+    pub target_features: Vec<String>,
+    /// The cargo profile the solutions were built with, e.g. `release`.
+    pub profile: String,
+}
+
+impl Environment {
+    /// Describes the current machine. Values that cannot be determined are `unknown`.
+    pub fn detect(profile: &str) -> Self {
+        let rustflags = rustflags();
+        let target_cpu = rustflags.as_deref().and_then(target_cpu);
+
+        let mut cfg = Command::new(rustc_path());
+        cfg.args(["--print", "cfg"]);
+        if let Some(target_cpu) = &target_cpu {
+            cfg.args(["-C", &format!("target-cpu={target_cpu}")]);
+        }
+
+        Environment {
+            cpu: cpu_model().unwrap_or_else(|| "unknown".into()),
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            governor: read_trimmed("/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor"),
+            kernel: output(Command::new("uname").arg("-sr"))
+                .unwrap_or_else(|| env::consts::OS.into()),
+            rustc: output(Command::new(rustc_path()).arg("--version"))
+                .unwrap_or_else(|| "unknown".into()),
+            target_features: output(&mut cfg)
+                .map(|cfg| target_features(&cfg))
+                .unwrap_or_default(),
+            target_cpu,
+            profile: profile.into(),
+        }
+    }
+
+    /// Whether timings benched in `other` can be compared to timings benched here. Settings that vary between
+    /// runs on the same machine, like the governor, are ignored.
+    pub fn same_machine(&self, other: &Environment) -> bool {
+        self.cpu == other.cpu && self.kernel == other.kernel && self.rustc == other.rustc
+    }
+
+    /// A one-line summary, e.g. `AMD Ryzen 7 5800X (16 threads), rustc 1.85.0, target-cpu=native, release`.
+    pub fn summary(&self) -> String {
+        let rustc = self.rustc.split(" (").next().unwrap_or(&self.rustc);
+        let mut summary = format!("{} ({} threads), {rustc}", self.cpu, self.threads);
+        if let Some(target_cpu) = &self.target_cpu {
+            summary.push_str(&format!(", target-cpu={target_cpu}"));
+        }
+        summary.push_str(&format!(", {}", self.profile));
+        summary
+    }
+
+    /// Conditions that make timings slower or noisier than usual.
+    pub fn warnings(&self) -> Vec<Warning> {
+        let mut warnings = vec![];
+        if on_battery() {
+            warnings.push(Warning::Battery);
+        }
+        if let Some(governor) = &self.governor
+            && governor != "performance"
+        {
+            warnings.push(Warning::Governor(governor.clone()));
+        }
+        warnings
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Warning {
+    /// Laptops often throttle the CPU on battery power.
+    Battery,
+    /// A governor other than `performance` scales the frequency down while idle, and up only under load.
+    Governor(String),
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::Battery => write!(
+                f,
+                "benchmarking on battery power, the CPU may be throttled. Plug in the charger for stable timings."
+            ),
+            Warning::Governor(governor) => write!(
+                f,
+                "the CPU frequency governor is `{governor}`, not `performance`. Timings of short solutions may vary."
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn rustc_path() -> String {
+    env::var("RUSTC").unwrap_or_else(|_| "rustc".into())
+}
+
+fn output(cmd: &mut Command) -> Option<String> {
+    let output = cmd.output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn read_trimmed(path: impl AsRef<Path>) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// The rustflags solutions are built with: `RUSTFLAGS` if set, like cargo does, or `.cargo/config.toml`.
+fn rustflags() -> Option<String> {
+    env::var("RUSTFLAGS")
+        .ok()
+        .or_else(|| fs::read_to_string(".cargo/config.toml").ok())
+}
+
+/// Finds the value of `target-cpu=` in rustflags or in a cargo config.
+fn target_cpu(rustflags: &str) -> Option<String> {
+    let (_, rest) = rustflags.split_once("target-cpu=")?;
+    let cpu: String = rest
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    (!cpu.is_empty()).then_some(cpu)
+}
+
+/// Lists the target features of `rustc --print cfg`, e.g. `target_feature="avx2"`.
+fn target_features(cfg: &str) -> Vec<String> {
+    cfg.lines()
+        .filter_map(|line| line.strip_prefix("target_feature=\""))
+        .filter_map(|feature| feature.strip_suffix('"'))
+        .map(String::from)
+        .collect()
+}
+
+fn cpu_model() -> Option<String> {
+    if cfg!(target_os = "macos") {
+        return output(Command::new("sysctl").args(["-n", "machdep.cpu.brand_string"]));
+    }
+    cpu_model_from_cpuinfo(&fs::read_to_string("/proc/cpuinfo").ok()?)
+}
+
+/// The model of `/proc/cpuinfo`, which is named differently on x86 and ARM.
+fn cpu_model_from_cpuinfo(cpuinfo: &str) -> Option<String> {
+    ["model name", "Hardware", "Model", "CPU part"]
+        .iter()
+        .find_map(|key| {
+            cpuinfo.lines().find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                (name.trim() == *key && !value.trim().is_empty()).then(|| value.trim().to_string())
+            })
+        })
+}
+
+fn on_battery() -> bool {
+    if cfg!(target_os = "macos") {
+        return output(Command::new("pmset").args(["-g", "batt"]))
+            .is_some_and(|batt| batt.contains("'Battery Power'"));
+    }
+
+    fs::read_dir("/sys/class/power_supply")
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .any(|supply| {
+            let path = supply.path();
+            read_trimmed(path.join("type")).as_deref() == Some("Battery")
+                && read_trimmed(path.join("status")).as_deref() == Some("Discharging")
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Environment> for JsonValue {
+    fn from(value: &Environment) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let string_or_null = |value: &Option<String>| match value {
+            Some(x) => JsonValue::String(x.clone()),
+            None => JsonValue::Null,
+        };
+
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        map.insert("threads".into(), JsonValue::Number(value.threads as f64));
+        map.insert("governor".into(), string_or_null(&value.governor));
+        map.insert("kernel".into(), JsonValue::String(value.kernel.clone()));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("target_cpu".into(), string_or_null(&value.target_cpu));
+        map.insert(
+            "target_features".into(),
+            JsonValue::Array(
+                value
+                    .target_features
+                    .iter()
+                    .cloned()
+                    .map(JsonValue::String)
+                    .collect(),
+            ),
+        );
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Environment {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timings.environment to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!(
+                    "Expected timings.environment.{key} to be a string."
+                ))
+        };
+        let string_or_null = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v.get::<String>().cloned().map(Some).ok_or(format!(
+                "Expected timings.environment.{key} to be null or a string."
+            )),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let threads = json
+            .get("threads")
+            .and_then(|v| v.get::<f64>())
+            .map(|threads| *threads as usize)
+            .ok_or("Expected timings.environment.threads to be a number.")?;
+
+        let target_features = json
+            .get("target_features")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .and_then(|features| {
+                features
+                    .iter()
+                    .map(|feature| feature.get::<String>().cloned())
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or("Expected timings.environment.target_features to be an array of strings.")?;
+
+        Ok(Environment {
+            cpu: string("cpu")?,
+            threads,
+            governor: string_or_null("governor")?,
+            kernel: string("kernel")?,
+            rustc: string("rustc")?,
+            target_cpu: string_or_null("target_cpu")?,
+            target_features,
+            profile: string("profile")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Environment, cpu_model_from_cpuinfo, target_cpu, target_features};
+    use tinyjson::JsonValue;
+
+    fn get_mock_environment() -> Environment {
+        Environment {
+            cpu: "AMD Ryzen 7 5800X 8-Core Processor".into(),
+            threads: 16,
+            governor: Some("schedutil".into()),
+            kernel: "Linux 6.8.0".into(),
+            rustc: "rustc 1.85.0-nightly (a2545fd6f 2024-11-28)".into(),
+            target_cpu: Some("native".into()),
+            target_features: vec!["avx2".into(), "bmi2".into()],
+            profile: "release".into(),
+        }
+    }
+
+    #[test]
+    fn finds_target_cpu() {
+        let config = r#"
+[build]
+rustflags = ["-C", "target-cpu=native","-C", "force-frame-pointers=yes"]
+"#;
+        assert_eq!(target_cpu(config), Some("native".into()));
+        assert_eq!(
+            target_cpu("-C target-cpu=x86-64-v3 -C opt-level=3"),
+            Some("x86-64-v3".into())
+        );
+        assert_eq!(target_cpu("-C opt-level=3"), None);
+    }
+
+    #[test]
+    fn lists_target_features() {
+        let cfg = "panic=\"unwind\"\ntarget_arch=\"x86_64\"\ntarget_feature=\"avx2\"\ntarget_feature=\"bmi2\"\n";
+        assert_eq!(target_features(cfg), vec!["avx2", "bmi2"]);
+    }
+
+    #[test]
+    fn finds_cpu_models() {
+        let x86 = "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel\t\t: 33\nmodel name\t: AMD Ryzen 7 5800X 8-Core Processor\n";
+        assert_eq!(
+            cpu_model_from_cpuinfo(x86),
+            Some("AMD Ryzen 7 5800X 8-Core Processor".into())
+        );
+
+        let arm = "processor\t: 0\nBogoMIPS\t: 108.00\nCPU part\t: 0xd08\n\nModel\t\t: Raspberry Pi 4 Model B Rev 1.4\n";
+        assert_eq!(
+            cpu_model_from_cpuinfo(arm),
+            Some("Raspberry Pi 4 Model B Rev 1.4".into())
+        );
+        assert_eq!(cpu_model_from_cpuinfo("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn summarizes_environment() {
+        assert_eq!(
+            get_mock_environment().summary(),
+            "AMD Ryzen 7 5800X 8-Core Processor (16 threads), rustc 1.85.0-nightly, target-cpu=native, release"
+        );
+    }
+
+    #[test]
+    fn compares_machines() {
+        let here = get_mock_environment();
+        let governor = Environment {
+            governor: Some("performance".into()),
+            ..get_mock_environment()
+        };
+        let rustc = Environment {
+            rustc: "rustc 1.86.0".into(),
+            ..get_mock_environment()
+        };
+        assert!(here.same_machine(&governor));
+        assert!(!here.same_machine(&rustc));
+    }
+
+    #[test]
+    fn serializes_environment() {
+        let environment = get_mock_environment();
+        let json = JsonValue::from(&environment);
+        assert_eq!(Environment::try_from(&json), Ok(environment));
+    }
+}
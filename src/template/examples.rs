@@ -0,0 +1,237 @@
+/// Example inputs with their expected answers, read from `data/examples/NN/<name>.txt`.
+///
+/// An example file starts with front-matter that holds the expected answers and puzzle parameters:
+/// ```text
+/// ---
+/// part_1 = 40
+/// connections = 10
+/// ---
+/// 162,817,812
+/// ...
+/// ```
+/// Parts without an expected answer are not checked. Files without front-matter only contain the input.
+use std::collections::HashMap;
+use std::fs;
+
+use crate::template::Day;
+use crate::template::answers::Answers;
+use crate::template::config::{self, Error, Value, parse_toml};
+use crate::template::params::Params;
+use crate::template::runner::{Outcome, PartResult};
+
+static DELIMITER: &str = "---";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    /// Expected answers, keyed by part.
+    expected: HashMap<u8, String>,
+    /// Puzzle parameters, e.g. `connections = 10`.
+    params: Params,
+}
+
+impl Example {
+    /// Reads an example of a day. Panics if the file cannot be read or is invalid, as it is meant to be used in tests.
+    pub fn read(day: Day, name: &str) -> Self {
+        let path = format!("{}/{day}/{name}.txt", config::get().data_dir("examples"));
+        let text = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("could not open example file \"{path}\": {e}"));
+        Self::parse(name, &text).unwrap_or_else(|e| panic!("invalid example file \"{path}\": {e}"))
+    }
+
+    /// Splits an example file into its front-matter and input.
+    pub fn parse(name: &str, text: &str) -> Result<Self, Error> {
+        let mut example = Self {
+            name: name.to_string(),
+            input: text.to_string(),
+            expected: HashMap::new(),
+            params: Params::default(),
+        };
+
+        let Some(rest) = text
+            .strip_prefix(DELIMITER)
+            .and_then(|rest| rest.strip_prefix('\n'))
+        else {
+            return Ok(example);
+        };
+
+        let (front_matter, input) = match rest.find(&format!("\n{DELIMITER}\n")) {
+            Some(i) => (&rest[..i], &rest[i + DELIMITER.len() + 2..]),
+            None => {
+                let i = rest
+                    .strip_suffix(&format!("\n{DELIMITER}"))
+                    .map(str::len)
+                    .ok_or_else(|| {
+                        Error::Invalid("front-matter is not closed with `---`.".into())
+                    })?;
+                (&rest[..i], "")
+            }
+        };
+
+        for (key, value) in parse_toml(front_matter)? {
+            let value = match value {
+                Value::String(s) => s,
+                Value::Integer(i) => i.to_string(),
+                Value::Bool(b) => b.to_string(),
+            };
+
+            match key.as_str() {
+                "part_1" => {
+                    example.expected.insert(1, value);
+                }
+                "part_2" => {
+                    example.expected.insert(2, value);
+                }
+                _ if key.contains('.') => {
+                    return Err(Error::Invalid(format!(
+                        "front-matter must not contain sections, found `{key}`."
+                    )));
+                }
+                _ => example.params.set(&key, value),
+            }
+        }
+
+        example.input = input.to_string();
+        Ok(example)
+    }
+
+    /// The expected answer of a part, if known.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        self.expected.get(&part).map(String::as_str)
+    }
+
+    /// A puzzle parameter of this example.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.raw(name)
+    }
+
+    /// All puzzle parameters of this example.
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// Asserts that the result of a part matches the expected answer. Does nothing if no answer is expected.
+    pub fn check<R: PartResult>(&self, part: u8, result: R) {
+        let Some(expected) = self.expected(part) else {
+            return;
+        };
+
+        let name = &self.name;
+        match result.into_outcome() {
+            Outcome::Solved(answer) => {
+                let answer = answer.to_string();
+                assert!(
+                    Answers::matches(expected, &answer),
+                    "example `{name}`, part {part}: expected {expected}, got {answer}"
+                );
+            }
+            Outcome::NotImplemented => {
+                panic!(
+                    "example `{name}`, part {part}: expected {expected}, but the part is not implemented."
+                )
+            }
+            Outcome::Failed(e) => {
+                panic!(
+                    "example `{name}`, part {part}: expected {expected}, but the part failed: {e}"
+                )
+            }
+        }
+    }
+}
+
+/// Creates one test per example file in `data/examples/NN/`, checking `part_one` and `part_two` against the
+/// expected answers in the front-matter. A part without an expected answer is not run. Use inside the test
+/// module of a solution:
+/// ```ignore
+/// advent_of_code::example_tests!(8);
+/// ```
+/// The optional `parse = <function>` parameter matches the one of [`crate::solution!`].
+/// Parts receive the context of `context("examples")`, with the puzzle parameters of the front-matter.
+#[macro_export]
+macro_rules! example_tests {
+    ($day:literal) => {
+        $crate::example_tests!($day, parse = ::std::convert::identity::<&str>);
+    };
+    ($day:literal, parse = $parse:expr) => {
+        macro_rules! example_case {
+            ($test:ident, $name:literal) => {
+                #[test]
+                fn $test() {
+                    let example =
+                        $crate::template::examples::Example::read($crate::day!($day), $name);
+                    let input = $parse(&example.input);
+                    let mut ctx = context("examples");
+                    ctx.params.merge(example.params());
+
+                    // examples often only cover one part, and the other may not handle them.
+                    if example.expected(1).is_some() {
+                        example.check(
+                            1,
+                            $crate::template::runner::Part::call(&part_one, &input, &ctx),
+                        );
+                    }
+                    if example.expected(2).is_some() {
+                        example.check(
+                            2,
+                            $crate::template::runner::Part::call(&part_two, &input, &ctx),
+                        );
+                    }
+                }
+            };
+        }
+
+        // generated by `build.rs`, contains one `example_case!` per example file.
+        include!(concat!(env!("OUT_DIR"), "/examples/", $day, ".rs"));
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Example;
+
+    #[test]
+    fn parses_front_matter() {
+        let example = Example::parse(
+            "basic",
+            "---\npart_1 = 40\npart_2 = \"ABC\"\nconnections = 10\n---\n1,2,3\n4,5,6\n",
+        )
+        .unwrap();
+
+        assert_eq!(example.input, "1,2,3\n4,5,6\n");
+        assert_eq!(example.expected(1), Some("40"));
+        assert_eq!(example.expected(2), Some("ABC"));
+        assert_eq!(example.param("connections"), Some("10"));
+        assert_eq!(example.param("unknown"), None);
+    }
+
+    #[test]
+    fn parses_examples_without_front_matter() {
+        let example = Example::parse("plain", "1\n2\n").unwrap();
+        assert_eq!(example.input, "1\n2\n");
+        assert_eq!(example.expected(1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_unclosed_front_matter() {
+        Example::parse("broken", "---\npart_1 = 1\n1\n2\n").unwrap();
+    }
+
+    #[test]
+    fn checks_results() {
+        let example = Example::parse("basic", "---\npart_1 = 40\n---\n").unwrap();
+        example.check(1, Some(40));
+        // parts without an expected answer are not checked.
+        example.check(2, None::<u64>);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 40, got 41")]
+    fn panics_for_wrong_results() {
+        let example = Example::parse("basic", "---\npart_1 = 40\n---\n").unwrap();
+        example.check(1, Ok::<u64, String>(41));
+    }
+}
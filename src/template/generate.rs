@@ -0,0 +1,183 @@
+/// Random puzzle inputs, to test solutions beyond the example and the personal input.
+///
+/// A day registers a generator with `solution!(DAY, generate = generate)`:
+/// ```ignore
+/// pub fn generate(size: usize, rng: &mut Rng) -> String {
+///     (0..size).map(|_| format!("{}\n", rng.below(100))).collect()
+/// }
+/// ```
+/// `gen <day> --size <size> --seed <seed>` runs it and writes the input to `data/generated/`.
+use std::collections::BTreeSet;
+use std::ops::Range;
+use std::{env, process};
+
+use crate::template::Day;
+
+/// A small, seeded pseudo-random number generator (SplitMix64). The same seed always produces the same input.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`. Panics if `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "cannot pick a number below 0");
+        // the bias of the modulo is negligible for the sizes of puzzle inputs.
+        self.next_u64() % n
+    }
+
+    /// A number in `range`. Panics if the range is empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        range.start + self.below(range.end - range.start)
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    /// A random element of a slice. Panics if it is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+
+    /// `count` distinct numbers of `range` in ascending order. Panics if the range is too small.
+    pub fn distinct_sorted(&mut self, count: usize, range: Range<u64>) -> Vec<u64> {
+        let len = range.end.saturating_sub(range.start);
+        assert!(
+            count as u64 <= len,
+            "cannot pick {count} distinct numbers of {range:?}"
+        );
+
+        // Floyd's algorithm, which needs `count` picks even for large ranges.
+        let mut picked = BTreeSet::new();
+        for j in len - count as u64..len {
+            let n = self.below(j + 1);
+            if !picked.insert(n) {
+                picked.insert(j);
+            }
+        }
+        picked.into_iter().map(|n| range.start + n).collect()
+    }
+}
+
+/// Produces valid puzzle inputs. `size` is the main dimension of the input, e.g. its number of lines,
+/// as documented by each day.
+pub trait Generator {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String;
+}
+
+impl<F: Fn(usize, &mut Rng) -> String> Generator for F {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        self(size, rng)
+    }
+}
+
+/// The arguments `gen` passes to a solution: `--generate --size <size> --seed <seed>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Request {
+    pub size: usize,
+    pub seed: u64,
+}
+
+/// Returns the request if the solution was started by `gen`.
+pub fn request() -> Option<Request> {
+    let args: Vec<String> = env::args().collect();
+    if !args.iter().any(|arg| arg == "--generate") {
+        return None;
+    }
+
+    let value = |name: &str| {
+        let i = args.iter().position(|arg| arg == name)?;
+        args.get(i + 1)
+    };
+
+    let size = value("--size").and_then(|s| s.parse().ok());
+    let seed = value("--seed").and_then(|s| s.parse().ok());
+
+    match (size, seed) {
+        (Some(size), Some(seed)) => Some(Request { size, seed }),
+        _ => {
+            eprintln!("`--generate` requires `--size <size>` and `--seed <seed>`.");
+            process::exit(1);
+        }
+    }
+}
+
+/// Prints a generated input and exits. Called by `solution!` instead of running the parts.
+pub fn run(day: Day, request: Request, generator: Option<&dyn Generator>) -> ! {
+    let Some(generator) = generator else {
+        eprintln!(
+            "Day {day} has no input generator. Register one with `solution!({}, generate = generate)`.",
+            day.into_inner()
+        );
+        process::exit(1);
+    };
+
+    print!(
+        "{}",
+        generator.generate(request.size, &mut Rng::new(request.seed))
+    );
+    process::exit(0);
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Generator, Rng};
+
+    #[test]
+    fn is_deterministic() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.below(1000)).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+    }
+
+    #[test]
+    fn picks_distinct_numbers() {
+        let mut rng = Rng::new(1);
+        let picked = rng.distinct_sorted(10, 5..15);
+        assert_eq!(picked, (5..15).collect::<Vec<_>>());
+
+        let picked = rng.distinct_sorted(100, 0..1_000_000_000);
+        assert_eq!(picked.len(), 100);
+        assert!(picked.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn generates_from_functions() {
+        let generate = |size: usize, rng: &mut Rng| {
+            (0..size)
+                .map(|_| format!("{}\n", rng.range(10..20)))
+                .collect::<String>()
+        };
+        let input = generate.generate(3, &mut Rng::new(42));
+        assert_eq!(input.lines().count(), 3);
+        assert!(
+            input
+                .lines()
+                .all(|l| (10..20).contains(&l.parse().unwrap()))
+        );
+    }
+}
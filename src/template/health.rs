@@ -0,0 +1,100 @@
+/// Checks inputs for problems that make solutions fail in confusing ways.
+use std::fmt::Display;
+
+use crate::template::context::InputKind;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Problem {
+    /// The file is empty, e.g. a placeholder created by `scaffold`.
+    Empty,
+    /// An error page of the website was saved instead of the input.
+    Html,
+    /// Windows line endings, which leave a `\r` at the end of lines split on `\n`.
+    CrLf,
+    /// Downloaded inputs always end with a newline, so the end was probably cut off while copying.
+    NoTrailingNewline,
+}
+
+impl Problem {
+    /// Whether a solution cannot produce a meaningful answer for the input.
+    pub fn is_fatal(self) -> bool {
+        matches!(self, Problem::Empty | Problem::Html)
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::Empty => write!(f, "is empty."),
+            Problem::Html => write!(
+                f,
+                "is an HTML page, not a puzzle input. Was it downloaded without a valid session cookie?"
+            ),
+            Problem::CrLf => write!(
+                f,
+                "has Windows (CRLF) line endings. `lines()` handles them, but splitting on '\\n' leaves a '\\r' at the end of each line."
+            ),
+            Problem::NoTrailingNewline => write!(
+                f,
+                "does not end with a newline like downloaded inputs do. Was the end cut off while copying it?"
+            ),
+        }
+    }
+}
+
+/// Returns the problems of an input. An empty input has no other problems.
+pub fn check(input: &str, kind: InputKind) -> Vec<Problem> {
+    if input.trim().is_empty() {
+        return vec![Problem::Empty];
+    }
+
+    let start = input.trim_start().get(..15).unwrap_or_default();
+    if start.to_ascii_lowercase().starts_with("<!doctype html")
+        || start.to_ascii_lowercase().starts_with("<html")
+    {
+        return vec![Problem::Html];
+    }
+
+    let mut problems = vec![];
+
+    if input.contains("\r\n") {
+        problems.push(Problem::CrLf);
+    }
+
+    // examples are usually copied by hand and are fine without a trailing newline.
+    if kind == InputKind::Real && !input.ends_with('\n') {
+        problems.push(Problem::NoTrailingNewline);
+    }
+
+    problems
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Problem, check};
+    use crate::template::context::InputKind;
+
+    #[test]
+    fn accepts_valid_inputs() {
+        assert!(check("1,2\n3,4\n", InputKind::Real).is_empty());
+        assert!(check("1,2\n3,4", InputKind::Example).is_empty());
+    }
+
+    #[test]
+    fn detects_problems() {
+        assert_eq!(check("", InputKind::Real), vec![Problem::Empty]);
+        assert_eq!(check(" \n", InputKind::Example), vec![Problem::Empty]);
+        assert_eq!(
+            check("<!DOCTYPE html>\n<html lang=\"en-us\">", InputKind::Real),
+            vec![Problem::Html]
+        );
+        assert_eq!(
+            check("1,2\r\n3,4", InputKind::Real),
+            vec![Problem::CrLf, Problem::NoTrailingNewline]
+        );
+        assert!(Problem::Html.is_fatal());
+        assert!(!Problem::CrLf.is_fatal());
+    }
+}
@@ -0,0 +1,211 @@
+/// Writes test results as JUnit XML, the format understood by most CI systems and test dashboards.
+use std::fmt::Write;
+use std::time::Duration;
+
+pub struct TestSuite {
+    pub name: String,
+    pub cases: Vec<TestCase>,
+}
+
+pub struct TestCase {
+    pub name: String,
+    pub classname: String,
+    pub time: Duration,
+    pub outcome: CaseOutcome,
+}
+
+pub enum CaseOutcome {
+    Passed,
+    /// The test ran, but produced a wrong result.
+    Failure {
+        message: String,
+        details: String,
+    },
+    /// The test could not produce a result.
+    Error {
+        message: String,
+        details: String,
+    },
+    Skipped {
+        message: String,
+    },
+}
+
+#[derive(Default)]
+struct Counts {
+    tests: usize,
+    failures: usize,
+    errors: usize,
+    skipped: usize,
+    time: Duration,
+}
+
+impl Counts {
+    fn of<'a>(cases: impl Iterator<Item = &'a TestCase>) -> Self {
+        let mut counts = Counts::default();
+        for case in cases {
+            counts.tests += 1;
+            counts.time += case.time;
+            match case.outcome {
+                CaseOutcome::Passed => {}
+                CaseOutcome::Failure { .. } => counts.failures += 1,
+                CaseOutcome::Error { .. } => counts.errors += 1,
+                CaseOutcome::Skipped { .. } => counts.skipped += 1,
+            }
+        }
+        counts
+    }
+
+    fn attributes(&self) -> String {
+        format!(
+            "tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\"",
+            self.tests,
+            self.failures,
+            self.errors,
+            self.skipped,
+            self.time.as_secs_f64()
+        )
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // control characters other than whitespace are not allowed in XML 1.0.
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Renders test suites as a JUnit XML document.
+pub fn to_xml(name: &str, suites: &[TestSuite]) -> String {
+    let mut out = String::new();
+
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+    let total = Counts::of(suites.iter().flat_map(|s| &s.cases));
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"{}\" {}>",
+        escape(name),
+        total.attributes()
+    );
+
+    for suite in suites {
+        let counts = Counts::of(suite.cases.iter());
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{}\" {}>",
+            escape(&suite.name),
+            counts.attributes()
+        );
+
+        for case in &suite.cases {
+            let _ = write!(
+                out,
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.6}\"",
+                escape(&case.name),
+                escape(&case.classname),
+                case.time.as_secs_f64()
+            );
+
+            match &case.outcome {
+                CaseOutcome::Passed => out.push_str("/>\n"),
+                CaseOutcome::Failure { message, details } => {
+                    let _ = writeln!(
+                        out,
+                        ">\n      <failure message=\"{}\" type=\"WrongAnswer\">{}</failure>\n    </testcase>",
+                        escape(message),
+                        escape(details)
+                    );
+                }
+                CaseOutcome::Error { message, details } => {
+                    let _ = writeln!(
+                        out,
+                        ">\n      <error message=\"{}\" type=\"Error\">{}</error>\n    </testcase>",
+                        escape(message),
+                        escape(details)
+                    );
+                }
+                CaseOutcome::Skipped { message } => {
+                    let _ = writeln!(
+                        out,
+                        ">\n      <skipped message=\"{}\"/>\n    </testcase>",
+                        escape(message)
+                    );
+                }
+            }
+        }
+
+        out.push_str("  </testsuite>\n");
+    }
+
+    out.push_str("</testsuites>\n");
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{CaseOutcome, TestCase, TestSuite, to_xml};
+
+    #[test]
+    fn writes_junit_xml() {
+        let suites = [TestSuite {
+            name: "Day 01".into(),
+            cases: vec![
+                TestCase {
+                    name: "Part 1 (input)".into(),
+                    classname: "day01".into(),
+                    time: Duration::from_millis(2),
+                    outcome: CaseOutcome::Passed,
+                },
+                TestCase {
+                    name: "Part 2 (input)".into(),
+                    classname: "day01".into(),
+                    time: Duration::from_millis(1),
+                    outcome: CaseOutcome::Failure {
+                        message: "expected 3, got 4".into(),
+                        details: "expected: 3\nactual: <4>".into(),
+                    },
+                },
+                TestCase {
+                    name: "Part 2 (example)".into(),
+                    classname: "day01".into(),
+                    time: Duration::ZERO,
+                    outcome: CaseOutcome::Skipped {
+                        message: "no known answer".into(),
+                    },
+                },
+            ],
+        }];
+
+        let xml = to_xml("advent_of_code", &suites);
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+        assert!(xml.contains(
+            "<testsuites name=\"advent_of_code\" tests=\"3\" failures=\"1\" errors=\"0\" skipped=\"1\" time=\"0.003000\">"
+        ));
+        assert!(
+            xml.contains(
+                "<testcase name=\"Part 1 (input)\" classname=\"day01\" time=\"0.002000\"/>"
+            )
+        );
+        assert!(xml.contains(
+            "<failure message=\"expected 3, got 4\" type=\"WrongAnswer\">expected: 3\nactual: &lt;4&gt;</failure>"
+        ));
+        assert!(xml.contains("<skipped message=\"no known answer\"/>"));
+        assert!(xml.ends_with("</testsuites>\n"));
+    }
+}
@@ -0,0 +1,361 @@
+/// Private leaderboards, as returned by `https://adventofcode.com/{year}/leaderboard/private/view/{id}.json`.
+use std::collections::{BTreeMap, HashMap};
+
+use tinyjson::JsonValue;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_GOLD: &str = "\x1b[33m";
+const ANSI_SILVER: &str = "\x1b[90m";
+
+#[derive(Debug)]
+pub struct Leaderboard {
+    pub event: u16,
+    pub members: Vec<Member>,
+}
+
+#[derive(Debug)]
+pub struct Member {
+    pub id: u64,
+    /// Not set for anonymous users.
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u64,
+    pub last_star_ts: u64,
+    /// Timestamps of the stars of each day, for part one and part two.
+    pub days: BTreeMap<u8, [Option<u64>; 2]>,
+}
+
+impl Member {
+    fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+}
+
+/// Parses a leaderboard. Members are sorted by their rank.
+pub fn parse(json: &str) -> Result<Leaderboard, String> {
+    let value: JsonValue = json
+        .parse()
+        .map_err(|_| "Expected the leaderboard to be JSON.".to_string())?;
+    Leaderboard::try_from(&value)
+}
+
+impl TryFrom<&JsonValue> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected the leaderboard to be a JSON object.")?;
+
+        let event = json
+            .get("event")
+            .and_then(|v| v.get::<String>())
+            .and_then(|s| s.parse().ok())
+            .ok_or("Expected leaderboard.event to be a year.")?;
+
+        let mut members = json
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected leaderboard.members to be an object.")?
+            .values()
+            .map(Member::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+
+        Ok(Self { event, members })
+    }
+}
+
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected member to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|n| *n as u64)
+                .ok_or(format!("Expected member.{key} to be a number."))
+        };
+
+        let mut days = BTreeMap::new();
+        let levels = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected member.completion_day_level to be an object.")?;
+
+        for (day, parts) in levels {
+            let day: u8 = day
+                .parse()
+                .map_err(|_| format!("Expected \"{day}\" to be a day."))?;
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected the stars of a day to be an object.")?;
+
+            let star = |part: &str| {
+                parts
+                    .get(part)?
+                    .get::<HashMap<String, JsonValue>>()?
+                    .get("get_star_ts")?
+                    .get::<f64>()
+                    .map(|ts| *ts as u64)
+            };
+            days.insert(day, [star("1"), star("2")]);
+        }
+
+        Ok(Self {
+            id: number("id")?,
+            name: json.get("name").and_then(|v| v.get::<String>()).cloned(),
+            local_score: number("local_score")?,
+            stars: number("stars")?,
+            last_star_ts: number("last_star_ts")?,
+            days,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Unix timestamp at which a puzzle unlocks: midnight EST (UTC-5).
+fn unlock_ts(year: u16, day: u8) -> u64 {
+    let days = days_from_civil(i64::from(year), 12, i64::from(day));
+    (days * 86_400 + 5 * 3_600) as u64
+}
+
+/// Formats a duration like the personal stats of the site, e.g. `01:02:03` or `2d 01:02:03`.
+fn format_duration(seconds: u64) -> String {
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        rest / 3_600,
+        rest % 3_600 / 60,
+        rest % 60
+    );
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+/// Puzzles per event. There are 12 since 2025.
+fn days_in_event(year: u16) -> u8 {
+    if year >= 2025 { 12 } else { 25 }
+}
+
+/// Renders the ranking of all members, and the star times of a day.
+/// Shows the latest day anyone has a star on if `day` is not set.
+pub fn render(board: &Leaderboard, id: u64, day: Option<u8>, color: bool) -> String {
+    let paint = |code: &str, s: &str| {
+        if color {
+            format!("{code}{s}{ANSI_RESET}")
+        } else {
+            s.to_string()
+        }
+    };
+
+    let last_day = board
+        .members
+        .iter()
+        .filter_map(|m| m.days.keys().max())
+        .max()
+        .copied()
+        .unwrap_or(0);
+    let days = days_in_event(board.event).max(last_day);
+
+    let mut out = vec![
+        paint(
+            ANSI_BOLD,
+            &format!(
+                "Private leaderboard {id} · {} · {} members",
+                board.event,
+                board.members.len()
+            ),
+        ),
+        String::new(),
+    ];
+
+    let indent = " ".repeat(17);
+    let tens: String = (1..=days)
+        .map(|d| {
+            if d < 10 {
+                ' '
+            } else {
+                char::from(b'0' + d / 10)
+            }
+        })
+        .collect();
+    let ones: String = (1..=days).map(|d| char::from(b'0' + d % 10)).collect();
+    out.push(format!("{indent}{tens}").trim_end().to_string());
+    out.push(format!("{indent}{ones}"));
+
+    let mut rank = 0;
+    for (i, member) in board.members.iter().enumerate() {
+        // members with equal scores share a rank.
+        if i == 0 || board.members[i - 1].local_score != member.local_score {
+            rank = i + 1;
+        }
+
+        // `*` for both stars of a day, `+` for part one only.
+        let stars: String = (1..=days)
+            .map(|d| match member.days.get(&d) {
+                Some([Some(_), Some(_)]) => paint(ANSI_GOLD, "*"),
+                Some([Some(_), None]) => paint(ANSI_SILVER, "+"),
+                _ => paint(ANSI_SILVER, "·"),
+            })
+            .collect();
+
+        out.push(format!(
+            "{:>3}) {:>5} {:>4}  {stars}  {}",
+            rank,
+            member.local_score,
+            format!("{}*", member.stars),
+            member.display_name()
+        ));
+    }
+
+    let day = day.unwrap_or(last_day);
+    if day == 0 {
+        out.push(String::new());
+        out.push("Nobody has collected a star yet.".into());
+        return out.join("\n") + "\n";
+    }
+
+    let unlock = unlock_ts(board.event, day);
+    let mut times: Vec<(String, [Option<u64>; 2])> = board
+        .members
+        .iter()
+        .filter_map(|m| {
+            let parts = m.days.get(&day)?;
+            Some((
+                m.display_name(),
+                parts.map(|ts| ts.map(|ts| ts.saturating_sub(unlock))),
+            ))
+        })
+        .collect();
+
+    // faster part two first, then faster part one.
+    times.sort_by_key(|(_, [one, two])| (two.unwrap_or(u64::MAX), one.unwrap_or(u64::MAX)));
+
+    out.push(String::new());
+    out.push(paint(ANSI_BOLD, &format!("Day {day}")));
+    out.push(String::new());
+
+    if times.is_empty() {
+        out.push("Nobody has collected a star on this day.".into());
+        return out.join("\n") + "\n";
+    }
+
+    let width = times
+        .iter()
+        .map(|(n, _)| n.chars().count())
+        .max()
+        .unwrap_or(0);
+    let cell = |time: Option<u64>| time.map_or("-".into(), format_duration);
+    out.push(format!(
+        "  {:width$}  {:>12}  {:>12}  {:>12}",
+        "", "Part 1", "Part 2", "Delta"
+    ));
+
+    for (name, [one, two]) in times {
+        let delta = one.zip(two).map(|(one, two)| two.saturating_sub(one));
+        out.push(format!(
+            "  {name:width$}  {:>12}  {:>12}  {:>12}",
+            cell(one),
+            cell(two),
+            cell(delta)
+        ));
+    }
+
+    out.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_duration, parse, render, unlock_ts};
+
+    static FIXTURE: &str = include_str!("fixtures/leaderboard.json");
+
+    #[test]
+    fn parses_leaderboards() {
+        let board = parse(FIXTURE).unwrap();
+        assert_eq!(board.event, 2024);
+
+        let names: Vec<_> = board.members.iter().map(|m| m.display_name()).collect();
+        assert_eq!(
+            names,
+            vec!["Ada", "Grace", "(anonymous user #1003)", "Linus"]
+        );
+
+        let ada = &board.members[0];
+        assert_eq!(ada.local_score, 19);
+        assert_eq!(ada.days[&3], [Some(1733206405), None]);
+        assert!(parse("{\"members\": {}}").is_err());
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        // 2024-12-01T05:00:00Z
+        assert_eq!(unlock_ts(2024, 1), 1733029200);
+        assert_eq!(format_duration(154), "00:02:34");
+        assert_eq!(format_duration(270_800), "3d 03:13:20");
+    }
+
+    #[test]
+    fn renders_leaderboards() {
+        let board = parse(FIXTURE).unwrap();
+        let text = render(&board, 1001, None, false);
+
+        let expected = "\
+Private leaderboard 1001 · 2024 · 4 members
+
+                          1111111111222222
+                 1234567890123456789012345
+  1)    19   5*  **+······················  Ada
+  2)    14   4*  *·*······················  Grace
+  3)     2   1*  +························  (anonymous user #1003)
+  4)     0   0*  ·························  Linus
+
+Day 3
+
+               Part 1        Part 2         Delta
+  Grace      00:05:00      01:05:00      01:00:00
+  Ada        01:13:25             -             -
+";
+        assert_eq!(text, expected);
+
+        let day_one = render(&board, 1001, Some(1), false);
+        assert!(
+            day_one.contains("  Ada                         00:02:34      00:10:00      00:07:26")
+        );
+        assert!(
+            day_one.contains("  (anonymous user #1003)   3d 03:13:20             -             -")
+        );
+    }
+}
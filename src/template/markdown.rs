@@ -0,0 +1,358 @@
+/// Renders the puzzle descriptions downloaded by aoc-cli for the terminal.
+///
+/// Supports the subset of markdown that aoc-cli writes: ATX and setext headings, paragraphs, lists,
+/// fenced and indented code blocks, emphasis, inline code and links.
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Options of [`render`].
+pub struct Style {
+    /// Maximum width of wrapped paragraphs.
+    pub width: usize,
+    pub color: bool,
+}
+
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    Item(String),
+    Code(Vec<String>),
+}
+
+/// Returns the byte offset of the part two heading, if part two is unlocked.
+pub fn part_two_start(md: &str) -> Option<usize> {
+    let mut offset = 0;
+    for line in md.split_inclusive('\n') {
+        let text = line.trim_start_matches(['#', ' ', '\\']);
+        if text.starts_with("--- Part Two ---") {
+            return Some(offset);
+        }
+        offset += line.len();
+    }
+    None
+}
+
+fn is_rule(line: &str, c: char) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && line.chars().all(|x| x == c)
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    if let Some(rest) = line.strip_prefix(['*', '-', '+'])
+        && let Some(rest) = rest.strip_prefix(' ')
+    {
+        return Some(rest);
+    }
+
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        return line[digits..].strip_prefix(". ");
+    }
+    None
+}
+
+fn parse(md: &str) -> Vec<Block> {
+    let lines: Vec<&str> = md.lines().collect();
+    let mut blocks = vec![];
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+
+        if line.trim().is_empty() {
+            i += 1;
+        } else if line.trim_start().starts_with("```") {
+            let mut code = vec![];
+            i += 1;
+            while i < lines.len() && !lines[i].trim_start().starts_with("```") {
+                code.push(lines[i].to_string());
+                i += 1;
+            }
+            blocks.push(Block::Code(code));
+            i += 1;
+        } else if line.starts_with("    ") || line.starts_with('\t') {
+            let mut code = vec![];
+            while i < lines.len()
+                && (lines[i].starts_with("    ")
+                    || lines[i].starts_with('\t')
+                    || lines[i].trim().is_empty())
+            {
+                code.push(lines[i].trim_start_matches('\t').replacen("    ", "", 1));
+                i += 1;
+            }
+            while code.last().is_some_and(|l| l.trim().is_empty()) {
+                code.pop();
+            }
+            blocks.push(Block::Code(code));
+        } else if line.starts_with('#') {
+            blocks.push(Block::Heading(line.trim_start_matches('#').trim().into()));
+            i += 1;
+        } else if lines
+            .get(i + 1)
+            .is_some_and(|next| is_rule(next, '-') || is_rule(next, '='))
+        {
+            blocks.push(Block::Heading(line.trim().into()));
+            i += 2;
+        } else if let Some(item) = list_item(line) {
+            let mut text = item.to_string();
+            i += 1;
+            // continuation lines of the item are indented.
+            while i < lines.len() && lines[i].starts_with("  ") && list_item(lines[i]).is_none() {
+                text.push(' ');
+                text.push_str(lines[i].trim());
+                i += 1;
+            }
+            blocks.push(Block::Item(text));
+        } else if is_rule(line, '-') || is_rule(line, '*') {
+            i += 1;
+        } else {
+            let mut text = line.trim().to_string();
+            i += 1;
+            while i < lines.len()
+                && !lines[i].trim().is_empty()
+                && !lines[i].starts_with('#')
+                && !lines[i].trim_start().starts_with("```")
+                && list_item(lines[i]).is_none()
+            {
+                text.push(' ');
+                text.push_str(lines[i].trim());
+                i += 1;
+            }
+            blocks.push(Block::Paragraph(text));
+        }
+    }
+
+    blocks
+}
+
+/// Renders emphasis, inline code and links, and removes backslash escapes.
+fn inline(text: &str, color: bool) -> String {
+    let style = |code: &'static str| if color { code } else { "" };
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let (mut bold, mut italic) = (false, false);
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    out.push(next);
+                }
+            }
+            '*' | '_' if chars.peek() == Some(&c) => {
+                chars.next();
+                bold = !bold;
+                out.push_str(style(if bold { ANSI_BOLD } else { ANSI_RESET }));
+                if !bold && italic {
+                    out.push_str(style(ANSI_ITALIC));
+                }
+            }
+            '*' => {
+                italic = !italic;
+                out.push_str(style(if italic { ANSI_ITALIC } else { ANSI_RESET }));
+                if !italic && bold {
+                    out.push_str(style(ANSI_BOLD));
+                }
+            }
+            '`' => {}
+            '[' => {
+                // links are rendered as their text.
+                let rest: String = chars.clone().collect();
+                if let Some(end) = rest.find("](")
+                    && let Some(close) = rest[end..].find(')')
+                {
+                    out.push_str(&inline(&rest[..end], color));
+                    for _ in 0..rest[..end + close + 1].chars().count() {
+                        chars.next();
+                    }
+                } else {
+                    out.push('[');
+                }
+            }
+            c => out.push(c),
+        }
+    }
+
+    if bold || italic {
+        out.push_str(style(ANSI_RESET));
+    }
+    out
+}
+
+/// The number of characters of a string without ANSI escape codes.
+fn visible_len(s: &str) -> usize {
+    let mut len = 0;
+    let mut in_escape = false;
+    for c in s.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            _ => len += 1,
+        }
+    }
+    len
+}
+
+/// Wraps text at word boundaries, prefixing the first line with `first` and the others with `rest`.
+fn wrap(text: &str, width: usize, first: &str, rest: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first.to_string();
+    let mut empty = true;
+
+    for word in text.split(' ').filter(|w| !w.is_empty()) {
+        if !empty && visible_len(&line) + 1 + visible_len(word) > width {
+            lines.push(line);
+            line = rest.to_string();
+            empty = true;
+        }
+        if !empty {
+            line.push(' ');
+        }
+        line.push_str(word);
+        empty = false;
+    }
+
+    lines.push(line);
+    lines
+}
+
+/// Renders puzzle markdown as wrapped, styled text.
+pub fn render(md: &str, style: &Style) -> String {
+    let mut out: Vec<String> = vec![];
+    let bold = |s: &str| {
+        if style.color {
+            format!("{ANSI_BOLD}{s}{ANSI_RESET}")
+        } else {
+            s.to_string()
+        }
+    };
+
+    let mut in_list = false;
+
+    for block in parse(md) {
+        if in_list && !matches!(block, Block::Item(_)) {
+            out.push(String::new());
+        }
+        in_list = matches!(block, Block::Item(_));
+
+        match block {
+            Block::Heading(text) => {
+                if out.last().is_some_and(|l| !l.is_empty()) {
+                    out.push(String::new());
+                }
+                out.push(bold(&inline(&text, false)));
+                out.push(String::new());
+            }
+            Block::Paragraph(text) => {
+                out.extend(wrap(&inline(&text, style.color), style.width, "", ""));
+                out.push(String::new());
+            }
+            Block::Item(text) => {
+                out.extend(wrap(
+                    &inline(&text, style.color),
+                    style.width,
+                    "  • ",
+                    "    ",
+                ));
+            }
+            Block::Code(code) => {
+                if out.last().is_some_and(|l| !l.is_empty()) {
+                    out.push(String::new());
+                }
+                out.extend(code.iter().map(|line| format!("    {line}")));
+                out.push(String::new());
+            }
+        }
+    }
+
+    while out.last().is_some_and(String::is_empty) {
+        out.pop();
+    }
+    out.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Style, part_two_start, render};
+
+    static PUZZLE: &str = r"\--- Day 1: Historian Hysteria ---
+----------
+
+The *Chief Historian* is always present for the big Christmas sleigh launch, but nobody has seen him in months!
+
+For example:
+
+```
+3   4
+4   3
+```
+
+* The first item
+  continues here.
+* The [second](https://example.com) item
+
+Your puzzle answer was `11`.
+
+\--- Part Two ---
+----------
+
+Your analysis only confirmed what everyone feared: the two lists of location IDs are indeed very different.
+";
+
+    fn plain(width: usize) -> Style {
+        Style {
+            width,
+            color: false,
+        }
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let expected = "\
+--- Day 1: Historian Hysteria ---
+
+The Chief Historian is always present for
+the big Christmas sleigh launch, but nobody
+has seen him in months!
+
+For example:
+
+    3   4
+    4   3
+
+  • The first item continues here.
+  • The second item
+
+Your puzzle answer was 11.
+
+--- Part Two ---
+
+Your analysis only confirmed what everyone
+feared: the two lists of location IDs are
+indeed very different.
+";
+        assert_eq!(render(PUZZLE, &plain(44)), expected);
+    }
+
+    #[test]
+    fn renders_emphasis() {
+        let style = Style {
+            width: 80,
+            color: true,
+        };
+        assert_eq!(
+            render("a *b* **c**", &style),
+            "a \x1b[3mb\x1b[0m \x1b[1mc\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn finds_part_two() {
+        let start = part_two_start(PUZZLE).unwrap();
+        assert!(PUZZLE[start..].starts_with("\\--- Part Two ---"));
+        assert_eq!(part_two_start("\\--- Day 1: Test ---\n"), None);
+    }
+}
@@ -1,27 +1,103 @@
-use std::{env, fs};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
 
 pub mod aoc_cli;
+pub mod assumptions;
+pub mod cli;
 pub mod commands;
+pub mod config;
+pub mod context;
+pub mod differential;
+pub mod examples;
+pub mod generate;
+pub mod ocr;
+pub mod params;
+pub mod render;
 pub mod runner;
+pub mod scaling;
 
 pub use day::*;
 
+mod answers;
+mod charts;
 mod day;
+mod environment;
+mod health;
+mod junit;
+mod leaderboard;
+mod markdown;
 mod readme_benchmarks;
+mod readme_stars;
+mod report;
 mod run_multi;
+mod sealed;
+mod status;
+mod templates;
 mod timings;
+mod verification;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Why a data file could not be read.
+#[derive(Debug)]
+pub enum InputError {
+    NotFound(PathBuf),
+    IO(PathBuf, io::Error),
+    InvalidExample(PathBuf, config::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound(path) => {
+                write!(f, "could not open \"{}\": file not found.", path.display())
+            }
+            InputError::IO(path, e) => write!(f, "could not open \"{}\": {e}", path.display()),
+            InputError::InvalidExample(path, e) => {
+                write!(f, "invalid example file \"{}\": {e}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// The path of the data file of a day, e.g. `data/inputs/01.txt`.
+pub fn input_path(folder: &str, day: Day) -> PathBuf {
+    Path::new(&config::get().data_dir(folder)).join(format!("{day}.txt"))
+}
+
+/// Reads the data file of a day to a string.
+/// The front-matter of example files is skipped, see [`examples::Example`].
+/// Missing or empty files are read from the sealed archive if it contains them, see `inputs seal`.
+pub fn try_read_file(folder: &str, day: Day) -> Result<String, InputError> {
+    let path = input_path(folder, day);
+    let sealed = || sealed::read(folder, &format!("{day}.txt"));
+
+    let f = match fs::read_to_string(&path) {
+        Ok(f) if !f.is_empty() => f,
+        Ok(f) => sealed().unwrap_or(f),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            sealed().ok_or(InputError::NotFound(path.clone()))?
+        }
+        Err(e) => return Err(InputError::IO(path, e)),
+    };
+
+    if folder == "examples" {
+        return examples::Example::parse(&day.to_string(), &f)
+            .map(|example| example.input)
+            .map_err(|e| InputError::InvalidExample(path, e));
+    }
+    Ok(f)
+}
+
+/// Helper function that reads a text file to a string. Panics with the path if it cannot be read, see [`try_read_file`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file(folder, day).unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -29,29 +105,114 @@ pub fn read_file(folder: &str, day: Day) -> String {
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
-        .join(folder)
+        .join(config::get().data_dir(folder))
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    fs::read_to_string(&filepath)
+        .unwrap_or_else(|e| panic!("could not open \"{}\": {e}", filepath.display()))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// With `parse = <function>`, the input is parsed once and both parts receive a reference to the parsed value.
+/// Parsing is not included in the timings.
+///
+/// With `params = { name: value, .. }`, puzzle parameters are declared, with different values for the example
+/// in `example = { name: value, .. }` or the front-matter of the example file. Parts that take a second argument
+/// `ctx: &Context` can read them from `ctx.params`. The generated `context(folder)` function returns the context
+/// of a data folder for tests, e.g. `context("examples")`.
+///
+/// With `generate = <function>` after the other parameters, the day provides random inputs for `gen`,
+/// see [`generate::Generator`].
+///
+/// With `variants = { 1: [part_one_brute], .. }` after that, `diff` compares other implementations
+/// of the parts with each other and with `part_one` and `part_two`, see [`differential`].
+///
+/// With `assume = [<function>, ..]` as the last parameter, the day declares what it assumes about its input,
+/// see [`assumptions`]. Runs validate their input before the parts: debug builds every time, release builds only
+/// for inputs that have not passed before. Inputs that passed are remembered in `data/assumptions/<day>.txt`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    (
+        $day:expr, $(parse = $parse:expr,)?
+        params = { $( $key:ident : $value:expr ),* $(,)? }
+        $(, example = { $( $example_key:ident : $example_value:expr ),* $(,)? } )?
+        $(, generate = $gen:expr)? $(, variants = $variants:tt)? $(, assume = $assume:tt)?
+    ) => {
+        $crate::solution!(
+            @impl $day,
+            parse = $crate::solution!(@parse $($parse)?),
+            params = [ $( $key : $value ),* ],
+            example = [ $( $( $example_key : $example_value ),* )? ],
+            generate = [$($gen)?], variants = [$($variants)?], assume = [$($assume)?],
+            [part_one, 1] [part_two, 2]
+        );
+    };
+    ($day:expr, 1 $(, generate = $gen:expr)? $(, variants = $variants:tt)? $(, assume = $assume:tt)?) => {
+        $crate::solution!(@impl $day, generate = [$($gen)?], variants = [$($variants)?], assume = [$($assume)?], [part_one, 1]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr, 2 $(, generate = $gen:expr)? $(, variants = $variants:tt)? $(, assume = $assume:tt)?) => {
+        $crate::solution!(@impl $day, generate = [$($gen)?], variants = [$($variants)?], assume = [$($assume)?], [part_two, 2]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr, parse = $parse:expr $(, generate = $gen:expr)? $(, variants = $variants:tt)? $(, assume = $assume:tt)?) => {
+        $crate::solution!(
+            @impl $day, parse = $parse, generate = [$($gen)?], variants = [$($variants)?], assume = [$($assume)?], [part_one, 1] [part_two, 2]
+        );
     };
+    ($day:expr $(, generate = $gen:expr)? $(, variants = $variants:tt)? $(, assume = $assume:tt)?) => {
+        $crate::solution!(@impl $day, generate = [$($gen)?], variants = [$($variants)?], assume = [$($assume)?], [part_one, 1] [part_two, 2]);
+    };
+
+    (@parse) => { ::std::convert::identity::<&str> };
+    (@parse $parse:expr) => { $parse };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@generate) => { None };
+    (@generate $gen:expr) => { Some(&$gen as &dyn $crate::template::generate::Generator) };
+    (@variants $parse:expr, [ $( [$func:expr, $part:expr] )* ]) => { Vec::new() };
+    (
+        @variants $parse:expr, [ $( [$func:expr, $part:expr] )* ]
+        { $( $variant_part:literal : [ $( $variant:ident ),* $(,)? ] ),* $(,)? }
+    ) => {{
+        use $crate::template::differential::{Answer, Variant};
+        use $crate::template::runner::Part;
+
+        // registered variants first, as the first one is the reference.
+        let mut variants = vec![ $( $(
+            Variant::new(stringify!($variant), $variant_part, |input, ctx| {
+                let input = $parse(input);
+                Answer::of(Part::call(&$variant, &input, ctx))
+            }),
+        )* )* ];
+        $(
+            variants.push(Variant::new(stringify!($func), $part, |input, ctx| {
+                let input = $parse(input);
+                Answer::of(Part::call(&$func, &input, ctx))
+            }));
+        )*
+        variants
+    }};
+    (@assumptions) => { Vec::<$crate::template::assumptions::Assumption>::new() };
+    (@assumptions [ $( $assumption:ident ),* $(,)? ]) => {
+        vec![ $( $crate::template::assumptions::Assumption::new(stringify!($assumption), $assumption) ),* ]
+    };
+    (@impl $day:expr, generate = [$($gen:expr)?], variants = [$($variants:tt)?], assume = [$($assume:tt)?], $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(
+            @impl $day, parse = ::std::convert::identity::<&str>, generate = [$($gen)?], variants = [$($variants)?], assume = [$($assume)?], $( [$func, $part] )*
+        );
+    };
+    (@impl $day:expr, parse = $parse:expr, generate = [$($gen:expr)?], variants = [$($variants:tt)?], assume = [$($assume:tt)?], $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(
+            @impl $day, parse = $parse, params = [], example = [], generate = [$($gen)?], variants = [$($variants)?], assume = [$($assume)?], $( [$func, $part] )*
+        );
+    };
+    (
+        @impl $day:expr, parse = $parse:expr,
+        params = [ $( $key:ident : $value:expr ),* ],
+        example = [ $( $example_key:ident : $example_value:expr ),* ],
+        generate = [$($gen:expr)?], variants = [$($variants:tt)?], assume = [$($assume:tt)?],
+        $( [$func:expr, $part:expr] )*
+    ) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +220,67 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        /// The context of a run against a data folder, i.e. `inputs` or `examples`, with its puzzle parameters.
+        fn context(folder: &str) -> $crate::template::context::Context {
+            use $crate::template::context::{Context, InputKind};
+
+            #[allow(unused_mut)]
+            let mut params = $crate::template::params::Params::default();
+            $( params.set(stringify!($key), $value); )*
+            if folder == "examples" {
+                $( params.set(stringify!($example_key), $example_value); )*
+                params.merge(&$crate::template::params::from_example(DAY));
+            }
+            Context::new(InputKind::from_folder(folder), params)
+        }
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            if let Some(request) = $crate::template::generate::request() {
+                $crate::template::generate::run(DAY, request, $crate::solution!(@generate $($gen)?));
+            }
+            let assumptions = $crate::solution!(@assumptions $($assume)?);
+            if let Some(request) = $crate::template::differential::request() {
+                $crate::template::differential::run(
+                    DAY,
+                    request,
+                    $crate::solution!(@variants $parse, [ $( [$func, $part] )* ] $($variants)?),
+                    $crate::solution!(@generate $($gen)?),
+                    &assumptions,
+                    &context("inputs"),
+                );
+            }
+            if let Some(request) = $crate::template::scaling::request() {
+                $crate::template::scaling::run(
+                    DAY,
+                    request,
+                    $crate::solution!(@generate $($gen)?),
+                    &context("inputs"),
+                    &|part: u8, input: &str, ctx: &$crate::template::context::Context| {
+                        let input = $parse(input);
+                        $(
+                            if part == $part {
+                                return Some($crate::template::scaling::measure(|| Part::call(&$func, &input, ctx)));
+                            }
+                        )*
+                        None
+                    },
+                );
+            }
+            if let Some(files) = $crate::template::assumptions::request() {
+                $crate::template::assumptions::run(DAY, &assumptions, &files, &context);
+            }
+            let input = read_input(input_folder(), DAY);
+            let ctx = context(input_folder());
+            $crate::template::assumptions::validate(
+                DAY,
+                &$crate::template::input_path(input_folder(), DAY),
+                &assumptions,
+                &input,
+                &ctx,
+            );
+            let input = $parse(&input);
+            $( run_part($func, &input, &ctx, DAY, $part); )*
         }
     };
 }
@@ -0,0 +1,258 @@
+/// Recognises answers that are drawn as letter art, e.g. the output of a CRT or a message in the sky.
+/// Supports the two fonts used by Advent of Code: 4x6 letters and 6x10 letters.
+use mygrid::grid::Grid;
+
+const SMALL_FONT_HEIGHT: usize = 6;
+const LARGE_FONT_HEIGHT: usize = 10;
+
+/// Letters of the 4x6 font.
+const SMALL_FONT: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Letters of the 6x10 font.
+const LARGE_FONT: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Decodes letter art drawn with `#` (or `█`) for lit and `.` (or a space) for unlit pixels.
+/// Returns [`None`] if the input is not letter art or contains unknown glyphs.
+///
+/// ```
+/// # use advent_of_code::template::ocr;
+/// let art = ".##.\n#..#\n#..#\n####\n#..#\n#..#";
+/// assert_eq!(ocr::recognize(art), Some("A".to_string()));
+/// ```
+#[must_use]
+pub fn recognize(art: &str) -> Option<String> {
+    let pixels = art
+        .lines()
+        .map(|line| {
+            line.trim_end()
+                .chars()
+                .map(|c| match c {
+                    '#' | '█' => Some(true),
+                    '.' | ' ' => Some(false),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    recognize_pixels(&pixels)
+}
+
+/// Decodes letter art stored in a grid, `true` being a lit pixel.
+#[must_use]
+pub fn recognize_grid(grid: &Grid<bool>) -> Option<String> {
+    let pixels: Vec<Vec<bool>> = grid.iter_rows().map(<[bool]>::to_vec).collect();
+    recognize_pixels(&pixels)
+}
+
+fn recognize_pixels(pixels: &[Vec<bool>]) -> Option<String> {
+    let is_lit = |row: usize, col: usize| pixels[row].get(col).copied().unwrap_or(false);
+
+    // ignore blank rows around the art.
+    let first_row = pixels.iter().position(|r| r.contains(&true))?;
+    let last_row = pixels.iter().rposition(|r| r.contains(&true))?;
+    let rows = first_row..=last_row;
+
+    let font: &[(char, &str)] = match rows.clone().count() {
+        SMALL_FONT_HEIGHT => &SMALL_FONT,
+        LARGE_FONT_HEIGHT => &LARGE_FONT,
+        _ => return None,
+    };
+
+    let width = pixels.iter().map(Vec::len).max()?;
+    let is_column_blank = |col: usize| rows.clone().all(|row| !is_lit(row, col));
+
+    let mut text = String::new();
+    let mut col = 0;
+
+    while col < width {
+        if is_column_blank(col) {
+            col += 1;
+            continue;
+        }
+
+        let start = col;
+        while col < width && !is_column_blank(col) {
+            col += 1;
+        }
+
+        let glyph = rows
+            .clone()
+            .map(|row| {
+                (start..col)
+                    .map(|c| if is_lit(row, c) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let letter = font
+            .iter()
+            .find(|(_, art)| trim_glyph(art) == glyph)
+            .map(|(letter, _)| *letter)?;
+
+        text.push(letter);
+    }
+
+    Some(text)
+}
+
+/// Removes blank columns on both sides of a glyph, e.g. the first column of the small `I`.
+fn trim_glyph(glyph: &str) -> String {
+    let rows: Vec<&str> = glyph.lines().collect();
+    let is_blank = |col: usize| rows.iter().all(|r| r.as_bytes()[col] == b'.');
+    let width = rows[0].len();
+
+    let start = (0..width).find(|&c| !is_blank(c)).unwrap_or(0);
+    let end = (0..width).rfind(|&c| !is_blank(c)).map_or(width, |c| c + 1);
+
+    rows.iter()
+        .map(|r| &r[start..end])
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use mygrid::grid::Grid;
+
+    use super::{recognize, recognize_grid};
+
+    #[test]
+    fn recognizes_small_font() {
+        let art = [
+            "#..#..###.####.#.....##..",
+            "#..#...#..#....#....#..#.",
+            "####...#..###..#....#..#.",
+            "#..#...#..#....#....#..#.",
+            "#..#...#..#....#....#..#.",
+            "#..#..###.####.####..##..",
+        ]
+        .join("\n");
+        assert_eq!(recognize(&art), Some("HIELO".to_string()));
+    }
+
+    #[test]
+    fn recognizes_large_font() {
+        let art = [
+            "#....#..######",
+            "#....#..#.....",
+            "#....#..#.....",
+            "#....#..#.....",
+            "######..#####.",
+            "#....#..#.....",
+            "#....#..#.....",
+            "#....#..#.....",
+            "#....#..#.....",
+            "#....#..######",
+        ]
+        .join("\n");
+        assert_eq!(recognize(&art), Some("HE".to_string()));
+    }
+
+    #[test]
+    fn ignores_blank_borders() {
+        let art = "\n      \n  .##.\n  #..#\n  #..#\n  ####\n  #..#\n  #..#\n\n";
+        assert_eq!(recognize(art), Some("A".to_string()));
+    }
+
+    #[test]
+    fn recognizes_grids() {
+        let art = "####\n#...\n###.\n#...\n#...\n#...";
+        let grid = Grid::new_from_str(art, |c| c == '#');
+        assert_eq!(recognize_grid(&grid), Some("F".to_string()));
+    }
+
+    #[test]
+    fn rejects_non_art() {
+        assert_eq!(recognize("1234\n5678"), None);
+        assert_eq!(recognize(""), None);
+        assert_eq!(recognize("#\n#\n#"), None);
+    }
+
+    #[test]
+    fn rejects_unknown_glyphs() {
+        let art = "####\n####\n####\n####\n####\n####";
+        assert_eq!(recognize(art), None);
+    }
+}
@@ -0,0 +1,99 @@
+/// Named puzzle parameters, e.g. the number of connections in a puzzle whose example uses 10 instead of 1000.
+///
+/// Parameters are declared in `solution!` with separate values for the real input and the example:
+/// ```ignore
+/// advent_of_code::solution!(8, params = { connections: 1000 }, example = { connections: 10 });
+/// ```
+/// Example values can also be set in the front-matter of an example file, which takes precedence.
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::template::examples::Example;
+use crate::template::{Day, config};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: impl Display) {
+        self.0.insert(name.to_string(), value.to_string());
+    }
+
+    /// Overrides parameters with the ones in `other`.
+    pub fn merge(&mut self, other: &Params) {
+        self.0
+            .extend(other.0.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+
+    /// The raw value of a parameter.
+    pub fn raw(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// Returns a parameter parsed to `T`.
+    /// Panics if it is missing or cannot be parsed, as solutions cannot run without their parameters.
+    /// The lookup runs inside the benchmarked part, so call this once per part and not in a loop.
+    pub fn get<T: FromStr>(&self, name: &str) -> T {
+        let Some(value) = self.raw(name) else {
+            panic!(
+                "missing puzzle parameter `{name}`. Declare it in `solution!` or in the front-matter of the example."
+            );
+        };
+
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid value \"{value}\" for puzzle parameter `{name}`."))
+    }
+}
+
+/// The parameters in the front-matter of the default example file of a day, e.g. `data/examples/08.txt`.
+pub fn from_example(day: Day) -> Params {
+    let path = Path::new(&config::get().data_dir("examples")).join(format!("{day}.txt"));
+    let Ok(text) = fs::read_to_string(&path) else {
+        return Params::default();
+    };
+
+    match Example::parse(&day.to_string(), &text) {
+        Ok(example) => example.params().clone(),
+        Err(e) => panic!("invalid example file \"{}\": {e}", path.display()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Params;
+
+    #[test]
+    fn merges_params() {
+        let mut params = Params::default();
+        params.set("connections", 1000);
+        params.set("width", 71);
+
+        let mut example = Params::default();
+        example.set("connections", 10);
+        params.merge(&example);
+
+        assert_eq!(params.get::<usize>("connections"), 10);
+        assert_eq!(params.get::<u8>("width"), 71);
+        assert_eq!(params.raw("height"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "missing puzzle parameter `height`")]
+    fn panics_for_missing_params() {
+        Params::default().get::<usize>("height");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid value")]
+    fn panics_for_invalid_params() {
+        let mut params = Params::default();
+        params.set("connections", "many");
+        params.get::<usize>("connections");
+    }
+}
@@ -2,10 +2,14 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use std::path::Path;
+
 use crate::template::Day;
+use crate::template::charts::{self, Bar, PART_1_COLOR, PART_2_COLOR};
+use crate::template::config::{self, ReadmeConfig, TableStyle};
 use crate::template::timings::Timings;
 
-static MARKER: &str = "<!--- benchmarking table --->";
+pub(crate) static MARKER: &str = "<!--- benchmarking table --->";
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -14,6 +18,15 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(message) => write!(f, "{message}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -30,8 +43,8 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,23 +65,36 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(readme: &ReadmeConfig, timings: Timings, total_millis: f64) -> String {
+    let header = format!("{} Benchmarks", readme.heading);
+
+    let alignment = match readme.style {
+        TableStyle::Default => "| :---: | :---: | :---:  |",
+        TableStyle::Minimal => "| --- | --- | --- |",
+    };
+
+    let mut lines: Vec<String> = vec![readme.marker.clone(), header, String::new()];
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    if let Some(chart) = &readme.chart {
+        lines.push(format!("![Benchmarks](./{chart})"));
+        lines.push(String::new());
+    }
+
+    lines.push("| Day | Part 1 | Part 2 |".into());
+    lines.push(alignment.into());
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let day = match readme.style {
+            TableStyle::Default => format!(
+                "[Day {}]({})",
+                timing.day.into_inner(),
+                get_path_for_bin(timing.day)
+            ),
+            TableStyle::Minimal => format!("Day {}", timing.day.into_inner()),
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
+            "| {} | `{}` | `{}` |",
+            day,
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
         ));
@@ -76,31 +102,72 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(readme.marker.clone());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    readme: &ReadmeConfig,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let positions = locate_table(s, &readme.marker)?;
+    let table = construct_table(readme, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
+/// Log-scale bars for every benched part, with the total below.
+fn construct_chart(timings: &Timings, total_millis: f64) -> String {
+    let bars: Vec<Bar> = timings
+        .data
+        .iter()
+        .flat_map(|timing| {
+            [(1, PART_1_COLOR), (2, PART_2_COLOR)]
+                .into_iter()
+                .filter_map(move |(part, color)| {
+                    Some(Bar {
+                        label: format!("Day {} · {part}", timing.day.into_inner()),
+                        nanos: timing.part_nanos(part)?,
+                        color,
+                    })
+                })
+        })
+        .collect();
+
+    charts::log_bar_chart(&bars, 720.0, Some(&format!("Total: {total_millis:.2}ms")))
+}
+
+fn write_chart(path: &str, timings: &Timings, total_millis: f64) -> Result<(), Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, construct_chart(timings, total_millis))?;
+    Ok(())
+}
+
 pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let config = &config::get().readme;
+    let mut readme = String::from_utf8_lossy(&fs::read(&config.path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+    if let Some(chart) = &config.chart {
+        write_chart(chart, &timings, total_millis)?;
+    }
+    update_content(&mut readme, config, timings, total_millis)?;
+    fs::write(&config.path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{MARKER, construct_chart, update_content};
+    use crate::{
+        day,
+        template::config::{ReadmeConfig, TableStyle},
+        template::timings::{PartStatus, Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,21 +176,31 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
                     total_nanos: 3e+10,
+                    environment: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
                     total_nanos: 7e+10,
+                    environment: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
                     total_nanos: 9e+10,
+                    environment: None,
                 },
             ],
+            environments: vec![],
         }
     }
 
@@ -131,28 +208,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &ReadmeConfig::default(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &ReadmeConfig::default(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &ReadmeConfig::default(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &ReadmeConfig::default(), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &ReadmeConfig::default(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,13 +237,15 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &ReadmeConfig::default(), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
+            "![Benchmarks](./.assets/benchmarks.svg)",
+            "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
             "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
@@ -180,4 +259,36 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_minimal_benchmarks() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let readme = ReadmeConfig {
+            heading: "###".into(),
+            style: TableStyle::Minimal,
+            ..ReadmeConfig::default()
+        };
+        update_content(&mut s, &readme, get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.contains("### Benchmarks"), true);
+        assert_eq!(s.contains("| Day 1 | `10ms` | `20ms` |"), true);
+    }
+
+    #[test]
+    fn omits_disabled_chart() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let readme = ReadmeConfig {
+            chart: None,
+            ..ReadmeConfig::default()
+        };
+        update_content(&mut s, &readme, get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.contains("![Benchmarks]"), false);
+    }
+
+    #[test]
+    fn constructs_chart() {
+        let svg = construct_chart(&get_mock_timings(), 190.0);
+        assert_eq!(svg.matches("<rect").count(), 6);
+        assert_eq!(svg.contains("<title>Day 4 · 2: 50.0ms</title>"), true);
+        assert_eq!(svg.contains("Total: 190.00ms"), true);
+    }
 }
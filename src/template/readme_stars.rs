@@ -0,0 +1,156 @@
+/// Module that writes the stars of `status` to the readme.
+/// The table has the same format as the one written by the `advent-readme-stars` workflow, so both can update it.
+use std::fs;
+
+use crate::template::config::{self, ReadmeConfig};
+use crate::template::readme_benchmarks::Error;
+use crate::template::status::DayStatus;
+
+pub(crate) static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Returns the byte range of the marker and the table following it.
+fn locate_table(readme: &str) -> Result<(usize, usize), Error> {
+    let start = readme
+        .find(MARKER)
+        .ok_or_else(|| Error::Parser("Could not find stars marker.".into()))?;
+
+    let mut end = start + MARKER.len();
+    let mut rest = readme[end..].split_inclusive('\n');
+
+    // skip the rest of the marker line.
+    if let Some(line) = rest.next() {
+        end += line.len();
+    }
+
+    let lines: Vec<&str> = rest.collect();
+    let mut i = 0;
+
+    // an existing table consists of a heading, a blank line and the table rows.
+    if lines.get(i).is_some_and(|l| l.starts_with('#')) {
+        i += 1;
+        if lines.get(i).is_some_and(|l| l.trim().is_empty()) {
+            i += 1;
+        }
+        while lines.get(i).is_some_and(|l| l.starts_with('|')) {
+            i += 1;
+        }
+        end += lines[..i].iter().map(|l| l.len()).sum::<usize>();
+    }
+
+    Ok((start, end))
+}
+
+fn construct_table(readme: &ReadmeConfig, year: Option<u16>, statuses: &[DayStatus]) -> String {
+    let header = match year {
+        Some(year) => format!("{} {year} Results", readme.heading),
+        None => format!("{} Results", readme.heading),
+    };
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for status in statuses.iter().filter(|s| s.stars > 0) {
+        let day = status.day.into_inner();
+        let link = match year {
+            Some(year) => format!("[Day {day}](https://adventofcode.com/{year}/day/{day})"),
+            None => format!("Day {day}"),
+        };
+        let star = |part: u8| if status.has_star(part) { "⭐" } else { " " };
+        lines.push(format!("| {link} | {} | {} |", star(1), star(2)));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn update_content(
+    s: &mut String,
+    readme: &ReadmeConfig,
+    year: Option<u16>,
+    statuses: &[DayStatus],
+) -> Result<(), Error> {
+    let (start, end) = locate_table(s)?;
+    s.replace_range(start..end, &construct_table(readme, year, statuses));
+    Ok(())
+}
+
+pub fn update(statuses: &[DayStatus]) -> Result<(), Error> {
+    let config = config::get();
+    let mut readme = String::from_utf8_lossy(&fs::read(&config.readme.path)?).to_string();
+    update_content(&mut readme, &config.readme, config.year, statuses)?;
+    fs::write(&config.readme.path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{MARKER, update_content};
+    use crate::template::Day;
+    use crate::template::config::ReadmeConfig;
+    use crate::template::status::DayStatus;
+
+    fn get_mock_statuses() -> Vec<DayStatus> {
+        [2, 1, 0]
+            .into_iter()
+            .enumerate()
+            .map(|(i, stars)| DayStatus {
+                day: Day::new(i as u8 + 1).unwrap(),
+                scaffolded: true,
+                input: true,
+                example_answers: 0,
+                tests: None,
+                stars,
+                benched: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &ReadmeConfig::default(), None, &[]).unwrap();
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{MARKER}\n\nbar");
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            Some(2024),
+            &get_mock_statuses(),
+        )
+        .unwrap();
+        let expected = [
+            "foo",
+            MARKER,
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |",
+            "",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn updates_existing_stars() {
+        let mut s = format!("foo\n{MARKER}\n\nbar");
+        let statuses = get_mock_statuses();
+        update_content(&mut s, &ReadmeConfig::default(), None, &statuses[..1]).unwrap();
+        let once = s.clone();
+        update_content(&mut s, &ReadmeConfig::default(), None, &statuses).unwrap();
+        update_content(&mut s, &ReadmeConfig::default(), None, &statuses[..1]).unwrap();
+        assert_eq!(s, once);
+        assert_eq!(s.matches("## Results").count(), 1);
+    }
+}
@@ -0,0 +1,752 @@
+/// Renders solution results in the output format selected with `--format`.
+///
+/// Solutions run in child processes. The format is passed to them via the `AOC_FORMAT` environment variable,
+/// so that both the child and `run_multi` in the parent render with the same [`Renderer`].
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::{Write, stdout};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{env, mem};
+
+use tinyjson::JsonValue;
+
+use crate::template::status::{DayStatus, stars_per_part};
+use crate::template::timings::{PartStatus, Timing};
+use crate::template::verification::{Verdict, Verification};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, ocr};
+
+pub static FORMAT_ENV: &str = "AOC_FORMAT";
+
+static FORMAT: OnceLock<Format> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Coloured output with progress indicators. Colours are disabled if `NO_COLOR` is set.
+    Text,
+    /// Output without colours, symbols or progress indicators, suitable for logs.
+    Plain,
+    /// A single JSON document.
+    Json,
+}
+
+impl Format {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Format::Text => "text",
+            Format::Plain => "plain",
+            Format::Json => "json",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "plain" => Ok(Format::Plain),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format `{s}`.")),
+        }
+    }
+}
+
+/// Returns the output format of this process, read from `AOC_FORMAT` unless [`set_format`] was called before.
+pub fn format() -> Format {
+    *FORMAT.get_or_init(|| {
+        env::var(FORMAT_ENV)
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(Format::Text)
+    })
+}
+
+/// Sets the output format, e.g. from a command-line flag.
+/// Has no effect if the format was already accessed.
+pub fn set_format(format: Format) {
+    let _ = FORMAT.set(format);
+}
+
+/// Returns a renderer for the output format of this process.
+pub fn renderer() -> Box<dyn Renderer> {
+    match format() {
+        Format::Text => Box::new(TextRenderer {
+            color: !env::var("NO_COLOR").is_ok_and(|s| !s.is_empty()),
+            symbols: true,
+        }),
+        Format::Plain => Box::new(TextRenderer {
+            color: false,
+            symbols: false,
+        }),
+        Format::Json => Box::new(JsonRenderer::default()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The result of running a single solution part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    pub part: u8,
+    pub status: PartStatus,
+    pub answer: Option<String>,
+    /// The error message followed by its sources, if the part failed.
+    pub errors: Vec<String>,
+    /// Intermediate values recorded with [`crate::template::context::Context::record`].
+    pub records: Vec<(String, String)>,
+    pub duration: Duration,
+    pub samples: u128,
+}
+
+impl PartReport {
+    /// Whether the part was benched, i.e. its duration is worth storing.
+    pub fn is_benched(&self) -> bool {
+        self.samples > 1
+    }
+}
+
+/// Receives results and output of solutions and prints them.
+pub trait Renderer {
+    /// A part produced a result and is about to be benched.
+    fn progress(&mut self, report: &PartReport);
+
+    /// A part finished running.
+    fn part(&mut self, report: &PartReport);
+
+    /// A line of output of a child process running a solution.
+    fn output(&mut self, line: &str);
+
+    /// `run_multi` starts running a day.
+    fn day(&mut self, day: Day);
+
+    /// `run_multi` skipped a day that has not been scaffolded.
+    fn day_not_solved(&mut self, day: Day);
+
+    /// All days ran. `total_millis` is set if solutions were benched.
+    fn summary(&mut self, timings: &[Timing], total_millis: Option<f64>);
+
+    /// `verify` compared the result of a part with its known answer.
+    fn verification(&mut self, verification: &Verification);
+
+    /// `verify` finished.
+    fn verification_summary(&mut self, verifications: &[Verification]);
+
+    /// `status` gathered the progress of all days.
+    fn status(&mut self, statuses: &[DayStatus]);
+}
+
+/* -------------------------------------------------------------------------- */
+
+struct TextRenderer {
+    color: bool,
+    symbols: bool,
+}
+
+impl TextRenderer {
+    fn bold(&self, s: impl Display) -> String {
+        if self.color {
+            format!("{ANSI_BOLD}{s}{ANSI_RESET}")
+        } else {
+            s.to_string()
+        }
+    }
+
+    fn italic(&self, s: impl Display) -> String {
+        if self.color {
+            format!("{ANSI_ITALIC}{s}{ANSI_RESET}")
+        } else {
+            s.to_string()
+        }
+    }
+
+    fn line(&self, report: &PartReport, duration_str: &str) -> String {
+        let part = format!("Part {}", report.part);
+
+        match (report.status, &report.answer) {
+            (PartStatus::Solved, Some(answer)) if answer.contains('\n') => {
+                let marker = if self.symbols { "▼ " } else { "" };
+                match ocr::recognize(answer) {
+                    Some(text) => format!("{part}: {marker}{}{duration_str}", self.bold(text)),
+                    None if self.symbols => format!("{part}: {marker}{duration_str}"),
+                    None => format!("{part}: letter art{duration_str}"),
+                }
+            }
+            (PartStatus::Solved, Some(answer)) => {
+                format!("{part}: {}{duration_str}", self.bold(answer))
+            }
+            (PartStatus::Failed, _) => match (self.symbols, report.errors.first()) {
+                (true, Some(err)) => format!("{part}: ✖ error: {err}"),
+                (false, Some(err)) => format!("{part}: error: {err}"),
+                (true, None) => format!("{part}: ✖"),
+                (false, None) => format!("{part}: error"),
+            },
+            _ if self.symbols => format!("{part}: ✖"),
+            _ => format!("{part}: not implemented"),
+        }
+    }
+}
+
+fn format_duration(duration: &Duration, samples: u128) -> String {
+    if samples == 1 {
+        format!(" ({duration:.1?})")
+    } else {
+        format!(" ({duration:.1?} @ {samples} samples)")
+    }
+}
+
+impl Renderer for TextRenderer {
+    fn progress(&mut self, report: &PartReport) {
+        // progress is overwritten with a carriage return, which is noise in plain output.
+        if self.symbols {
+            print!("{} > {}", self.line(report, ""), self.italic("benching"));
+            let _ = stdout().flush();
+        }
+    }
+
+    fn part(&mut self, report: &PartReport) {
+        if self.symbols {
+            print!("\r");
+        }
+
+        println!(
+            "{}",
+            self.line(report, &format_duration(&report.duration, report.samples))
+        );
+
+        if let Some(answer) = report.answer.as_ref().filter(|a| a.contains('\n')) {
+            println!("{answer}");
+        }
+
+        for cause in report.errors.iter().skip(1) {
+            println!("    caused by: {cause}");
+        }
+
+        for (name, value) in &report.records {
+            println!("    {}: {value}", self.italic(name));
+        }
+    }
+
+    fn output(&mut self, line: &str) {
+        println!("{line}");
+    }
+
+    fn day(&mut self, day: Day) {
+        println!("{}", self.bold(format!("Day {day}")));
+        println!("------");
+    }
+
+    fn day_not_solved(&mut self, _day: Day) {
+        println!("Not solved.");
+    }
+
+    fn summary(&mut self, timings: &[Timing], total_millis: Option<f64>) {
+        let statuses: Vec<(Day, u8, PartStatus)> = timings
+            .iter()
+            .flat_map(|t| [(t.day, 1, t.part_1_status), (t.day, 2, t.part_2_status)])
+            .collect();
+
+        let count = |status: PartStatus| statuses.iter().filter(|s| s.2 == status).count();
+
+        println!(
+            "\n{} {} solved, {} not implemented, {} failed",
+            self.bold("Summary:"),
+            count(PartStatus::Solved),
+            count(PartStatus::NotImplemented),
+            count(PartStatus::Failed)
+        );
+
+        let marker = if self.symbols { "✖ " } else { "" };
+        for (day, part, _) in statuses.iter().filter(|s| s.2 == PartStatus::Failed) {
+            println!("  {marker}Day {day}, Part {part} failed");
+        }
+
+        if let Some(total_millis) = total_millis {
+            println!(
+                "\n{} {}",
+                self.bold("Total (Run):"),
+                self.italic(format!("{total_millis:.2}ms"))
+            );
+        }
+    }
+
+    fn verification(&mut self, verification: &Verification) {
+        let (symbol, word) = match verification.verdict {
+            Verdict::Passed => ("✔", "ok"),
+            Verdict::Wrong { .. } | Verdict::Failed(_) => ("✖", "failed"),
+            Verdict::Unknown(_) => ("?", "unknown"),
+            Verdict::NotImplemented | Verdict::MissingInput => ("-", "skipped"),
+        };
+
+        let details = match &verification.verdict {
+            Verdict::Passed => String::new(),
+            Verdict::Wrong { expected, actual } => {
+                format!(" expected {expected}, got {}", actual.replace('\n', " "))
+            }
+            Verdict::Failed(errors) => format!(" error: {}", errors.join(": ")),
+            Verdict::NotImplemented => " not implemented".into(),
+            Verdict::Unknown(answer) => {
+                format!(" no known answer, got {}", answer.replace('\n', " "))
+            }
+            Verdict::MissingInput => " missing input file".into(),
+        };
+
+        println!(
+            "Day {}, Part {} ({}): {}{details}",
+            verification.day,
+            verification.part,
+            verification.input,
+            if self.symbols { symbol } else { word },
+        );
+    }
+
+    fn verification_summary(&mut self, verifications: &[Verification]) {
+        let count =
+            |f: fn(&Verdict) -> bool| verifications.iter().filter(|v| f(&v.verdict)).count();
+
+        println!(
+            "\n{} {} passed, {} wrong, {} failed, {} skipped",
+            self.bold("Summary:"),
+            count(|v| matches!(v, Verdict::Passed)),
+            count(|v| matches!(v, Verdict::Wrong { .. })),
+            count(|v| matches!(v, Verdict::Failed(_))),
+            count(|v| matches!(
+                v,
+                Verdict::NotImplemented | Verdict::Unknown(_) | Verdict::MissingInput
+            )),
+        );
+    }
+
+    fn status(&mut self, statuses: &[DayStatus]) {
+        let (star, no_star) = if self.symbols {
+            ("★", "☆")
+        } else {
+            ("*", ".")
+        };
+        let check = |value: Option<bool>| match (value, self.symbols) {
+            (Some(true), true) => "✔",
+            (Some(false), true) => "✖",
+            (None, true) => "·",
+            (Some(true), false) => "yes",
+            (Some(false), false) => "no",
+            (None, false) => "-",
+        };
+
+        // a calendar with five days per row.
+        for week in statuses.chunks(5) {
+            let row: Vec<String> = week
+                .iter()
+                .map(|status| {
+                    let stars: String = (1..=2)
+                        .map(|part| if status.has_star(part) { star } else { no_star })
+                        .collect();
+                    format!("{} {stars}", status.day)
+                })
+                .collect();
+            println!("{}", row.join("   "));
+        }
+
+        let [part_1, part_2] = stars_per_part(statuses);
+        println!(
+            "\n{} {} (part 1: {part_1}, part 2: {part_2})",
+            self.bold("Stars:"),
+            part_1 + part_2
+        );
+
+        let started: Vec<&DayStatus> = statuses.iter().filter(|s| s.is_started()).collect();
+        if started.is_empty() {
+            return;
+        }
+
+        println!(
+            "\n{}",
+            self.bold("Day  Scaffolded  Input  Examples  Tests  Stars  Benched")
+        );
+        for status in started {
+            println!(
+                "{:<4} {:<11} {:<6} {:<9} {:<6} {:<6} {}",
+                status.day.to_string(),
+                check(Some(status.scaffolded)),
+                check(Some(status.input)),
+                format!("{}/2", status.example_answers),
+                check(status.tests),
+                format!("{}/2", status.stars),
+                check(Some(status.benched)),
+            );
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Prints one JSON line per part in child processes, and collects these lines into a single
+/// document in the parent process.
+#[derive(Default)]
+struct JsonRenderer {
+    reports: Vec<PartReport>,
+}
+
+impl Renderer for JsonRenderer {
+    fn progress(&mut self, _report: &PartReport) {}
+
+    fn part(&mut self, report: &PartReport) {
+        println!("{}", JsonValue::from(report).stringify().unwrap());
+    }
+
+    fn output(&mut self, line: &str) {
+        match parse_report(line) {
+            Some(report) => self.reports.push(report),
+            // keep stdout valid JSON, e.g. when a solution prints debug output.
+            None => eprintln!("{line}"),
+        }
+    }
+
+    fn day(&mut self, _day: Day) {}
+
+    fn day_not_solved(&mut self, _day: Day) {}
+
+    fn summary(&mut self, _timings: &[Timing], total_millis: Option<f64>) {
+        let reports = mem::take(&mut self.reports);
+
+        let mut days: Vec<(Day, Vec<JsonValue>)> = vec![];
+        for report in &reports {
+            match days.last_mut() {
+                Some((day, parts)) if *day == report.day => parts.push(report.into()),
+                _ => days.push((report.day, vec![report.into()])),
+            }
+        }
+
+        let count = |status: PartStatus| {
+            JsonValue::Number(reports.iter().filter(|r| r.status == status).count() as f64)
+        };
+
+        let summary = HashMap::from([
+            ("solved".to_string(), count(PartStatus::Solved)),
+            (
+                "not_implemented".to_string(),
+                count(PartStatus::NotImplemented),
+            ),
+            ("failed".to_string(), count(PartStatus::Failed)),
+        ]);
+
+        let days = days
+            .into_iter()
+            .map(|(day, parts)| {
+                JsonValue::Object(HashMap::from([
+                    ("day".to_string(), JsonValue::String(day.to_string())),
+                    ("parts".to_string(), JsonValue::Array(parts)),
+                ]))
+            })
+            .collect();
+
+        let document = HashMap::from([
+            ("days".to_string(), JsonValue::Array(days)),
+            ("summary".to_string(), JsonValue::Object(summary)),
+            (
+                "total_millis".to_string(),
+                total_millis.map_or(JsonValue::Null, JsonValue::Number),
+            ),
+        ]);
+
+        println!("{}", JsonValue::Object(document).stringify().unwrap());
+    }
+
+    fn verification(&mut self, _verification: &Verification) {}
+
+    fn verification_summary(&mut self, verifications: &[Verification]) {
+        let count = |f: fn(&Verdict) -> bool| {
+            JsonValue::Number(verifications.iter().filter(|v| f(&v.verdict)).count() as f64)
+        };
+
+        let summary = HashMap::from([
+            (
+                "passed".to_string(),
+                count(|v| matches!(v, Verdict::Passed)),
+            ),
+            (
+                "wrong".to_string(),
+                count(|v| matches!(v, Verdict::Wrong { .. })),
+            ),
+            (
+                "failed".to_string(),
+                count(|v| matches!(v, Verdict::Failed(_))),
+            ),
+            (
+                "skipped".to_string(),
+                count(|v| {
+                    matches!(
+                        v,
+                        Verdict::NotImplemented | Verdict::Unknown(_) | Verdict::MissingInput
+                    )
+                }),
+            ),
+        ]);
+
+        let document = HashMap::from([
+            (
+                "verifications".to_string(),
+                JsonValue::Array(verifications.iter().map(JsonValue::from).collect()),
+            ),
+            ("summary".to_string(), JsonValue::Object(summary)),
+        ]);
+
+        println!("{}", JsonValue::Object(document).stringify().unwrap());
+    }
+
+    fn status(&mut self, statuses: &[DayStatus]) {
+        let [part_1, part_2] = stars_per_part(statuses);
+
+        let stars = HashMap::from([
+            ("part_1".to_string(), JsonValue::Number(part_1 as f64)),
+            ("part_2".to_string(), JsonValue::Number(part_2 as f64)),
+        ]);
+
+        let document = HashMap::from([
+            (
+                "days".to_string(),
+                JsonValue::Array(statuses.iter().map(JsonValue::from).collect()),
+            ),
+            ("stars".to_string(), JsonValue::Object(stars)),
+        ]);
+
+        println!("{}", JsonValue::Object(document).stringify().unwrap());
+    }
+}
+
+/// Parses a line printed by a child process in JSON mode.
+pub fn parse_report(line: &str) -> Option<PartReport> {
+    if !line.starts_with('{') {
+        return None;
+    }
+
+    JsonValue::from_str(line)
+        .ok()
+        .and_then(|json| PartReport::try_from(&json).ok())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(answer) => JsonValue::String(answer.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "errors".into(),
+            JsonValue::Array(
+                value
+                    .errors
+                    .iter()
+                    .map(|e| JsonValue::String(e.clone()))
+                    .collect(),
+            ),
+        );
+        map.insert(
+            "records".into(),
+            JsonValue::Array(
+                value
+                    .records
+                    .iter()
+                    .map(|(name, value)| {
+                        JsonValue::Object(HashMap::from([
+                            ("name".into(), JsonValue::String(name.clone())),
+                            ("value".into(), JsonValue::String(value.clone())),
+                        ]))
+                    })
+                    .collect(),
+            ),
+        );
+        map.insert(
+            "time_nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .copied()
+                .ok_or(format!("Expected report.{key} to be a number."))
+        };
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .and_then(|s| PartStatus::from_str(s).ok())
+            .ok_or("Expected report.status to be a part status.")?;
+
+        let answer = json.get("answer").and_then(|v| v.get::<String>()).cloned();
+
+        let errors = json
+            .get("errors")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected report.errors to be an array.")?
+            .iter()
+            .filter_map(|v| v.get::<String>().cloned())
+            .collect();
+
+        // reports of older versions have no records.
+        let records = json
+            .get("records")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .into_iter()
+            .flatten()
+            .filter_map(|record| {
+                let record = record.get::<HashMap<String, JsonValue>>()?;
+                let field = |key: &str| record.get(key)?.get::<String>().cloned();
+                Some((field("name")?, field("value")?))
+            })
+            .collect();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartReport {
+            day,
+            part: number("part")? as u8,
+            status,
+            answer,
+            errors,
+            records,
+            duration: Duration::from_nanos(number("time_nanos")? as u64),
+            samples: number("samples")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Verification> for JsonValue {
+    fn from(value: &Verification) -> Self {
+        let string = |s: &str| JsonValue::String(s.into());
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), string(&value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("input".into(), string(value.input));
+        map.insert(
+            "time_nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+
+        let (verdict, expected, actual, errors) = match &value.verdict {
+            Verdict::Passed => ("passed", None, None, vec![]),
+            Verdict::Wrong { expected, actual } => (
+                "wrong",
+                Some(expected.as_str()),
+                Some(actual.as_str()),
+                vec![],
+            ),
+            Verdict::Failed(errors) => ("failed", None, None, errors.clone()),
+            Verdict::NotImplemented => ("not_implemented", None, None, vec![]),
+            Verdict::Unknown(answer) => ("unknown", None, Some(answer.as_str()), vec![]),
+            Verdict::MissingInput => ("missing_input", None, None, vec![]),
+        };
+
+        map.insert("verdict".into(), string(verdict));
+        map.insert("expected".into(), expected.map_or(JsonValue::Null, string));
+        map.insert("actual".into(), actual.map_or(JsonValue::Null, string));
+        map.insert(
+            "errors".into(),
+            JsonValue::Array(errors.into_iter().map(JsonValue::String).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&DayStatus> for JsonValue {
+    fn from(value: &DayStatus) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("scaffolded".into(), JsonValue::Boolean(value.scaffolded));
+        map.insert("input".into(), JsonValue::Boolean(value.input));
+        map.insert(
+            "example_answers".into(),
+            JsonValue::Number(f64::from(value.example_answers)),
+        );
+        map.insert(
+            "tests".into(),
+            value.tests.map_or(JsonValue::Null, JsonValue::Boolean),
+        );
+        map.insert("stars".into(), JsonValue::Number(f64::from(value.stars)));
+        map.insert("benched".into(), JsonValue::Boolean(value.benched));
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{Format, PartReport, parse_report};
+    use crate::{day, template::timings::PartStatus};
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert_eq!("plain".parse::<Format>(), Ok(Format::Plain));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn roundtrips_reports() {
+        let report = PartReport {
+            day: day!(3),
+            part: 2,
+            status: PartStatus::Failed,
+            answer: None,
+            errors: vec!["invalid input".into(), "invalid digit".into()],
+            records: vec![("nodes".into(), "12".into()), ("edges".into(), "3".into())],
+            duration: Duration::from_nanos(1500),
+            samples: 1,
+        };
+
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(parse_report(&line), Some(report));
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(parse_report("Part 1: 42 (1.0ms)"), None);
+        assert_eq!(parse_report("{ not json"), None);
+        assert_eq!(parse_report("{\"debug\": 1}"), None);
+    }
+}
@@ -0,0 +1,302 @@
+/// Renders stored timings as a self-contained HTML page that works offline, without any scripts.
+use std::fmt::Write;
+
+use crate::template::charts::{
+    Bar, PART_1_COLOR, PART_2_COLOR, bar_chart, format_nanos, line_chart, log_bar_chart,
+};
+use crate::template::timings::Timings;
+
+/// Timings as they were stored at some commit.
+pub struct Snapshot {
+    /// Describes the commit, e.g. `a1b2c3d (2024-12-08)`.
+    pub label: String,
+    pub timings: Timings,
+}
+
+pub struct Report<'a> {
+    pub title: String,
+    pub timings: &'a Timings,
+    /// Earlier versions of the timings, oldest first.
+    pub history: &'a [Snapshot],
+    /// Where the timings were captured, as label and value.
+    pub environment: &'a [(String, String)],
+}
+
+const STYLE: &str = "\
+body { font-family: ui-sans-serif, system-ui, sans-serif; max-width: 960px; margin: 2rem auto; padding: 0 1rem; color: #222; }
+h1, h2 { font-weight: 600; }
+section { margin-bottom: 2.5rem; }
+.days { display: grid; grid-template-columns: repeat(auto-fill, minmax(220px, 1fr)); gap: 1rem; }
+.day, .history { border: 1px solid #ddd; border-radius: 6px; padding: 0.5rem 0.75rem; }
+.day h3, .history h3 { margin: 0 0 0.25rem; font-size: 1rem; }
+.legend span { display: inline-block; width: 0.8em; height: 0.8em; margin: 0 0.3em 0 1em; }
+table { border-collapse: collapse; }
+td, th { text-align: left; padding: 0.2rem 1rem 0.2rem 0; }
+.muted { color: #888; }
+";
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+impl Report<'_> {
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{STYLE}</style>\n</head>\n<body>",
+            escape(&self.title)
+        );
+        let _ = writeln!(out, "<h1>{}</h1>", escape(&self.title));
+        let _ = writeln!(
+            out,
+            "<p class=\"legend\"><span style=\"background: {PART_1_COLOR}\"></span>Part 1<span style=\"background: {PART_2_COLOR}\"></span>Part 2</p>"
+        );
+
+        if self.timings.data.is_empty() {
+            out.push_str("<p class=\"muted\">No timings stored yet.</p>\n");
+        } else {
+            self.write_overview(&mut out);
+            self.write_days(&mut out);
+        }
+
+        self.write_history(&mut out);
+        self.write_environment(&mut out);
+
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    fn write_overview(&self, out: &mut String) {
+        let bars: Vec<Bar> = self
+            .timings
+            .data
+            .iter()
+            .flat_map(|timing| {
+                [(1, PART_1_COLOR), (2, PART_2_COLOR)]
+                    .into_iter()
+                    .filter_map(move |(part, color)| {
+                        Some(Bar {
+                            label: format!("Day {} · {part}", timing.day),
+                            nanos: timing.part_nanos(part)?,
+                            color,
+                        })
+                    })
+            })
+            .collect();
+
+        let total = format!("Total: {:.2}ms", self.timings.total_millis());
+        let _ = writeln!(
+            out,
+            "<section>\n<h2>All parts <small class=\"muted\">(log scale)</small></h2>\n{}</section>",
+            log_bar_chart(&bars, 900.0, Some(&total))
+        );
+    }
+
+    fn write_days(&self, out: &mut String) {
+        out.push_str("<section>\n<h2>Days</h2>\n<div class=\"days\">\n");
+
+        for timing in &self.timings.data {
+            let bars: Vec<Bar> = [(1, PART_1_COLOR), (2, PART_2_COLOR)]
+                .into_iter()
+                .filter_map(|(part, color)| {
+                    Some(Bar {
+                        label: format!("Part {part}"),
+                        nanos: timing.part_nanos(part)?,
+                        color,
+                    })
+                })
+                .collect();
+
+            let _ = writeln!(
+                out,
+                "<div class=\"day\">\n<h3>Day {} <small class=\"muted\">{}</small></h3>\n{}</div>",
+                timing.day,
+                format_nanos(timing.total_nanos),
+                bar_chart(&bars, 200.0, 140.0)
+            );
+        }
+
+        out.push_str("</div>\n</section>\n");
+    }
+
+    fn write_history(&self, out: &mut String) {
+        out.push_str("<section>\n<h2>History</h2>\n");
+
+        // a single snapshot does not make a history.
+        if self.history.len() < 2 {
+            out.push_str(
+                "<p class=\"muted\">Not available, timings need to be committed more than once.</p>\n</section>\n",
+            );
+            return;
+        }
+
+        out.push_str("<div class=\"days\">\n");
+
+        for timing in &self.timings.data {
+            for (part, color) in [(1, PART_1_COLOR), (2, PART_2_COLOR)] {
+                let points: Vec<(String, f64)> = self
+                    .history
+                    .iter()
+                    .filter_map(|snapshot| {
+                        let nanos = snapshot
+                            .timings
+                            .data
+                            .iter()
+                            .find(|t| t.day == timing.day)?
+                            .part_nanos(part)?;
+                        Some((snapshot.label.clone(), nanos))
+                    })
+                    .collect();
+
+                if points.len() < 2 {
+                    continue;
+                }
+
+                let _ = writeln!(
+                    out,
+                    "<div class=\"history\">\n<h3>Day {} · Part {part}</h3>\n{}</div>",
+                    timing.day,
+                    line_chart(&points, 300.0, 120.0, color)
+                );
+            }
+        }
+
+        out.push_str("</div>\n</section>\n");
+    }
+
+    fn write_environment(&self, out: &mut String) {
+        out.push_str("<section>\n<h2>Environment</h2>\n");
+
+        if self.environment.is_empty() {
+            out.push_str("<p class=\"muted\">Not recorded.</p>\n");
+        } else {
+            out.push_str("<table>\n");
+            for (label, value) in self.environment {
+                let _ = writeln!(
+                    out,
+                    "<tr><th>{}</th><td>{}</td></tr>",
+                    escape(label),
+                    escape(value)
+                );
+            }
+            out.push_str("</table>\n");
+        }
+
+        out.push_str("</section>\n");
+    }
+}
+
+/// Keeps only snapshots in which some timing changed, so that re-runs of `time --store` do not flatten the charts.
+pub fn dedup_history(history: Vec<Snapshot>) -> Vec<Snapshot> {
+    let mut out: Vec<Snapshot> = vec![];
+
+    for snapshot in history {
+        let changed = out.last().is_none_or(|last| {
+            let key = |t: &Timings| {
+                t.data
+                    .iter()
+                    .map(|t| (t.day, t.part_1.clone(), t.part_2.clone()))
+                    .collect::<Vec<_>>()
+            };
+            key(&last.timings) != key(&snapshot.timings)
+        });
+
+        if changed {
+            out.push(snapshot);
+        }
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Report, Snapshot, dedup_history};
+    use crate::day;
+    use crate::template::timings::{PartStatus, Timing, Timings};
+
+    fn timings(part_1: &str) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some(part_1.into()),
+                part_2: Some("2.5ms".into()),
+                part_1_status: PartStatus::Solved,
+                part_2_status: PartStatus::Solved,
+                total_nanos: 2_510_000.0,
+                environment: None,
+            }],
+            environments: vec![],
+        }
+    }
+
+    #[test]
+    fn renders_html_report() {
+        let current = timings("10.0µs");
+        let history = [
+            Snapshot {
+                label: "aaaaaaa".into(),
+                timings: timings("20.0µs"),
+            },
+            Snapshot {
+                label: "bbbbbbb".into(),
+                timings: timings("10.0µs"),
+            },
+        ];
+        let environment = [("CPU".to_string(), "<unknown>".to_string())];
+
+        let html = Report {
+            title: "Advent of Code 2024".into(),
+            timings: &current,
+            history: &history,
+            environment: &environment,
+        }
+        .to_html();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("src=\"http"));
+        assert!(html.contains("Total: 2.51ms"));
+        assert!(html.contains("<h3>Day 01 · Part 1</h3>"));
+        assert!(html.contains("<title>aaaaaaa: 20.0µs</title>"));
+        assert!(html.contains("<td>&lt;unknown&gt;</td>"));
+    }
+
+    #[test]
+    fn renders_empty_report() {
+        let html = Report {
+            title: "Advent of Code".into(),
+            timings: &Timings::default(),
+            history: &[],
+            environment: &[],
+        }
+        .to_html();
+
+        assert!(html.contains("No timings stored yet."));
+        assert!(html.contains("Not available"));
+        assert!(html.contains("Not recorded."));
+    }
+
+    #[test]
+    fn drops_unchanged_snapshots() {
+        let snapshot = |label: &str, part_1: &str| Snapshot {
+            label: label.into(),
+            timings: timings(part_1),
+        };
+
+        let history = dedup_history(vec![
+            snapshot("a", "1.0µs"),
+            snapshot("b", "1.0µs"),
+            snapshot("c", "2.0µs"),
+        ]);
+
+        let labels: Vec<&str> = history.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, ["a", "c"]);
+    }
+}
@@ -1,6 +1,6 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::{Day, render};
 
 use super::{
     all_days,
@@ -9,6 +9,7 @@ use super::{
 
 pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut renderer = render::renderer();
 
     let mut need_space = false;
 
@@ -16,18 +17,18 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     all_days()
         .filter(|day| days_to_run.contains(day))
         .for_each(|day| {
-            if need_space {
+            if need_space && render::format() != render::Format::Json {
                 println!();
             }
             need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            renderer.day(day);
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output =
+                child_commands::run_solution(day, is_timed, is_release, renderer.as_mut()).unwrap();
 
             if output.is_empty() {
-                println!("Not solved.");
+                renderer.day_not_solved(day);
             } else {
                 let val = child_commands::parse_exec_time(&output, day);
                 timings.push(val);
@@ -35,13 +36,14 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         });
 
     if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
+        let timings = Timings {
+            data: timings,
+            environments: vec![],
+        };
+        renderer.summary(&timings.data, Some(timings.total_millis()));
         Some(timings)
     } else {
+        renderer.summary(&timings, None);
         None
     }
 }
@@ -69,6 +71,8 @@ pub fn get_path_for_bin(day: Day) -> String {
 pub mod child_commands {
     use super::{Error, get_path_for_bin};
     use crate::template::Day;
+    use crate::template::render::{self, PartReport, Renderer};
+    use crate::template::timings::{PartStatus, parse_duration};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,19 +80,33 @@ pub mod child_commands {
         thread,
     };
 
+    /// The cargo profile solutions are built with.
+    #[must_use]
+    pub fn profile(is_release: bool) -> &'static str {
+        if is_release { "release" } else { "dev" }
+    }
+
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        renderer: &mut dyn Renderer,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
         let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
+        let mut args = vec![
+            "run",
+            "--quiet",
+            "--bin",
+            &day_padded,
+            "--profile",
+            profile(is_release),
+        ];
 
         if is_timed {
             // mirror `--time` flag to child invocations.
@@ -101,6 +119,7 @@ pub mod child_commands {
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .env(render::FORMAT_ENV, render::format().as_str())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -118,7 +137,7 @@ pub mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+            renderer.output(&line);
             output.push(line);
         }
 
@@ -128,14 +147,76 @@ pub mod child_commands {
         Ok(output)
     }
 
+    /// Run the solution bin for a given day against an input kind and collect the results of its parts.
+    pub fn run_reports(day: Day, is_release: bool, input: &str) -> Result<Vec<PartReport>, Error> {
+        let day_padded = day.to_string();
+        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        args.extend(["--", "--input", input]);
+
+        let mut cmd = Command::new("cargo")
+            .args(&args)
+            .env(render::FORMAT_ENV, render::Format::Json.as_str())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+
+        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
+
+        let mut reports = vec![];
+
+        for line in stdout.lines() {
+            let line = line?;
+            match render::parse_report(&line) {
+                Some(report) => reports.push(report),
+                None => eprintln!("{line}"),
+            }
+        }
+
+        cmd.wait()?;
+
+        Ok(reports)
+    }
+
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
+            part_1_status: PartStatus::NotImplemented,
+            part_2_status: PartStatus::NotImplemented,
             total_nanos: 0_f64,
+            environment: None,
         };
 
+        // output of children in JSON mode.
+        for report in output.iter().filter_map(|l| render::parse_report(l)) {
+            let duration = report
+                .is_benched()
+                .then(|| format!("{:.1?}", report.duration));
+
+            if report.is_benched() {
+                timings.total_nanos += report.duration.as_nanos() as f64;
+            }
+
+            match report.part {
+                1 => (timings.part_1, timings.part_1_status) = (duration, report.status),
+                _ => (timings.part_2, timings.part_2_status) = (duration, report.status),
+            }
+        }
+
+        output
+            .iter()
+            .filter_map(|l| parse_status(l))
+            .for_each(|(part, status)| match part {
+                1 => timings.part_1_status = status,
+                _ => timings.part_2_status = status,
+            });
+
         output
             .iter()
             .filter_map(|l| {
@@ -164,12 +245,31 @@ pub mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Parses the final result line of a part, e.g. `Part 1: ✖ error: invalid input`.
+    fn parse_status(line: &str) -> Option<(u8, PartStatus)> {
+        // intermediate results are overwritten with a carriage return, only look at the final one.
+        let line = line.rsplit('\r').next()?;
+
+        let (part, result) = line.split_once(": ")?;
+        let part = match part {
+            "Part 1" => 1,
+            "Part 2" => 2,
+            _ => return None,
+        };
+
+        // plain output spells out the status instead of using symbols.
+        let status = if result.starts_with("✖ error:") || result.starts_with("error") {
+            PartStatus::Failed
+        } else if result.starts_with('✖') || result == "not implemented" {
+            PartStatus::NotImplemented
+        } else {
+            PartStatus::Solved
+        };
+
+        Some((part, status))
     }
 
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
@@ -179,14 +279,7 @@ pub mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        Some((str_timing, parse_duration(str_timing)?))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -207,7 +300,7 @@ pub mod child_commands {
     mod tests {
         use super::parse_exec_time;
 
-        use crate::day;
+        use crate::{day, template::timings::PartStatus};
 
         #[test]
         fn parses_execution_times() {
@@ -252,6 +345,54 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.part_1_status, PartStatus::NotImplemented);
+            assert_eq!(res.part_2_status, PartStatus::NotImplemented);
+        }
+
+        #[test]
+        fn parses_part_statuses() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 42 > benching\rPart 1: 42 (74.13ns @ 100000 samples)".into(),
+                    "Part 2: ✖\rPart 2: ✖ error: invalid digit found in string".into(),
+                    "    caused by: Part 2: ✖".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1_status, PartStatus::Solved);
+            assert_eq!(res.part_2_status, PartStatus::Failed);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn parses_plain_output() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: not implemented".into(),
+                    "Part 2: error: invalid digit found in string".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1_status, PartStatus::NotImplemented);
+            assert_eq!(res.part_2_status, PartStatus::Failed);
+        }
+
+        #[test]
+        fn parses_json_output() {
+            let res = parse_exec_time(
+                &[
+                    r#"{"day":"01","part":1,"status":"solved","answer":"42","errors":[],"time_nanos":1500,"samples":100}"#.into(),
+                    r#"{"day":"01","part":2,"status":"failed","answer":null,"errors":["oops"],"time_nanos":10,"samples":1}"#.into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 1500_f64);
+            assert_eq!(res.part_1.unwrap(), "1.5µs");
+            assert_eq!(res.part_1_status, PartStatus::Solved);
+            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.part_2_status, PartStatus::Failed);
         }
     }
 }
@@ -1,23 +1,197 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
+use std::error::Error;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{Write, stdout};
+use std::io::{IsTerminal, Write, stdin, stdout};
 use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::answers::Answers;
+use crate::template::commands::download;
+use crate::template::context::{Context, InputKind};
+use crate::template::health;
+use crate::template::render::{self, Format, PartReport};
+use crate::template::timings::PartStatus;
+use crate::template::{Day, InputError, aoc_cli, config, input_path, ocr, try_read_file};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// The outcome of running a single solution part.
+pub enum Outcome<T> {
+    /// The part produced an answer.
+    Solved(T),
+    /// The part returned `None`, i.e. it has not been implemented yet.
+    NotImplemented,
+    /// The part returned an error.
+    Failed(Box<dyn Error>),
+}
+
+/// Return types accepted for solution parts.
+///
+/// Parts can either return `Option<T>`, where `None` means "not implemented yet",
+/// or `Result<T, E>` for solutions that can fail, e.g. while parsing their input.
+pub trait PartResult {
+    type Answer: Display;
+
+    fn into_outcome(self) -> Outcome<Self::Answer>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        match self {
+            Some(answer) => Outcome::Solved(answer),
+            None => Outcome::NotImplemented,
+        }
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        match self {
+            Ok(answer) => Outcome::Solved(answer),
+            Err(err) => Outcome::Failed(err.into()),
+        }
+    }
+}
+
+/// The message of a panic caught with `catch_unwind`.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".into()
+    }
+}
+
+/// Solution parts, which take the input and optionally the [`Context`] of the run:
+/// ```ignore
+/// pub fn part_one(input: &str) -> Option<u64>
+/// pub fn part_two(input: &str, ctx: &Context) -> Option<u64>
+/// ```
+/// `M` only tells the two signatures apart.
+pub trait Part<I, M> {
+    type Output: PartResult;
+
+    fn call(&self, input: I, ctx: &Context) -> Self::Output;
+}
+
+impl<F: Fn(I) -> R, I, R: PartResult> Part<I, ()> for F {
+    type Output = R;
+
+    fn call(&self, input: I, _: &Context) -> R {
+        self(input)
+    }
+}
+
+impl<F: Fn(I, &Context) -> R, I, R: PartResult> Part<I, Context> for F {
+    type Output = R;
+
+    fn call(&self, input: I, ctx: &Context) -> R {
+        self(input, ctx)
+    }
+}
+
+/// Returns the data folder of the input to run against: `examples` if `--input example` is passed, `inputs` otherwise.
+pub fn input_folder() -> &'static str {
+    let args: Vec<String> = env::args().collect();
+    match args
+        .iter()
+        .position(|x| x == "--input")
+        .map(|i| args.get(i + 1))
+    {
+        Some(Some(kind)) if kind == "example" => "examples",
+        _ => "inputs",
+    }
+}
+
+/// Reads the input of a run and checks it for problems, see [`health::check`].
+/// Offers to download a missing or empty input. Exits if the input cannot be used.
+pub fn read_input(folder: &str, day: Day) -> String {
+    let kind = InputKind::from_folder(folder);
+    let path = input_path(folder, day);
+
+    let mut input = try_read_file(folder, day);
+    let is_missing = match &input {
+        Err(InputError::NotFound(_)) => true,
+        Ok(input) => input.trim().is_empty(),
+        Err(_) => false,
+    };
+
+    if kind == InputKind::Real && is_missing && offer_download(day) {
+        input = try_read_file(folder, day);
+    }
+
+    let input = input.unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        if kind == InputKind::Real {
+            eprintln!("Download it with `cargo download {day}`.");
+        }
+        process::exit(1);
+    });
+
+    for problem in health::check(&input, kind) {
+        let level = if problem.is_fatal() {
+            "Error"
+        } else {
+            "Warning"
+        };
+        eprintln!("{level}: \"{}\" {problem}", path.display());
+        if problem.is_fatal() {
+            if kind == InputKind::Real {
+                eprintln!("Download it with `cargo download {day}`.");
+            }
+            process::exit(1);
+        }
+    }
+
+    input
+}
+
+/// Asks whether to download a missing input. Only asks in interactive sessions with aoc-cli installed.
+fn offer_download(day: Day) -> bool {
+    if !stdin().is_terminal() || render::format() == Format::Json || aoc_cli::check().is_err() {
+        return false;
+    }
+
+    if !confirm(&format!(
+        "The input of day {day} is missing. Download it now?"
+    )) {
+        return false;
+    }
+
+    match download::download(day, &[]) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("failed to call aoc-cli: {e}");
+            false
+        }
+    }
+}
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+pub fn run_part<I: Copy, M>(func: impl Part<I, M>, input: I, ctx: &Context, day: Day, part: u8) {
+    let mut renderer = render::renderer();
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let (outcome, duration, samples) = run_timed(
+        |input| func.call(input, ctx).into_outcome(),
+        input,
+        |outcome| {
+            renderer.progress(&report(outcome, day, part, Duration::ZERO, 1));
+            ctx.set_benching(true);
+        },
+    );
+    ctx.set_benching(false);
 
-    if let Some(result) = result {
+    let mut report = report(&outcome, day, part, duration, samples);
+    report.records = ctx.take_records();
+    renderer.part(&report);
+
+    if let Outcome::Solved(result) = outcome {
         submit_result(result, day, part);
     }
 }
@@ -25,11 +199,13 @@ pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, da
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Parts that did not produce an answer are never benched. `hook` is called before benching.
 fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
+    func: impl Fn(I) -> Outcome<T>,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    mut hook: impl FnMut(&Outcome<T>),
+) -> (Outcome<T>, Duration, u128) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -39,9 +215,10 @@ fn run_timed<I: Copy, T>(
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let is_solved = matches!(result, Outcome::Solved(_));
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if is_solved && std::env::args().any(|x| x == "--time") {
+        hook(&result);
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
@@ -50,19 +227,21 @@ fn run_timed<I: Copy, T>(
     (result, run.0, run.1)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+pub(crate) fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Duration, u128) {
+    let config = &config::get().bench;
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 100000);
+    let bench_iterations = (Duration::from_millis(config.budget_ms).as_nanos()
+        / cmp::max(base_time.as_nanos(), 10))
+    .clamp(config.min_samples, config.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
     // warm up
-    for _ in 0..100 {
+    for _ in 0..config.warmup {
         black_box(func(black_box(input)));
     }
 
@@ -89,52 +268,43 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
-    }
-}
-
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
-
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+fn report<T: Display>(
+    outcome: &Outcome<T>,
+    day: Day,
+    part: u8,
+    duration: Duration,
+    samples: u128,
+) -> PartReport {
+    let (status, answer, errors) = match outcome {
+        Outcome::Solved(answer) => (PartStatus::Solved, Some(answer.to_string()), vec![]),
+        Outcome::NotImplemented => (PartStatus::NotImplemented, None, vec![]),
+        Outcome::Failed(err) => {
+            let mut errors = vec![err.to_string()];
+            let mut source = err.source();
+            while let Some(cause) = source {
+                errors.push(cause.to_string());
+                source = cause.source();
             }
+            (PartStatus::Failed, None, errors)
         }
+    };
+
+    PartReport {
+        day,
+        part,
+        status,
+        answer,
+        errors,
+        records: vec![],
+        duration,
+        samples,
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
+///  1. submitting is enabled and, if required, we are in `--release` mode.
 ///  2. aoc-cli is installed.
+///  3. the submission is confirmed, if `submit.confirm` is set.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
@@ -162,6 +332,46 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    // `solve` forwards its `--year` override, which takes precedence over `aoc.toml`.
+    if let Some(year) = args
+        .iter()
+        .position(|x| x == "--year")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok())
+    {
+        let mut config = config::load();
+        config.year = Some(year);
+        config::init(config);
+    }
+
+    let config = &config::get().submit;
+
+    if !config.enabled {
+        eprintln!(
+            "Submitting is disabled by `submit.enabled` in \"{}\".",
+            config::CONFIG_FILE_PATH
+        );
+        process::exit(1);
+    }
+
+    if config.require_release && cfg!(debug_assertions) {
+        eprintln!("Refusing to submit an answer computed by a debug build. Run with `--release`.");
+        process::exit(1);
+    }
+
+    let mut answer = result.to_string();
+
+    // letter art is submitted as the text it spells.
+    if answer.contains('\n') {
+        let Some(text) = ocr::recognize(&answer) else {
+            eprintln!(
+                "Multi-line answers can only be submitted if they are recognised as letter art."
+            );
+            process::exit(1);
+        };
+        answer = text;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!(
             "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
@@ -169,6 +379,34 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
+    if config.confirm && !confirm(&format!("Submit \"{answer}\" for part {part}?")) {
+        println!("Submission cancelled.");
+        return None;
+    }
+
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let result = aoc_cli::submit(day, part, &answer);
+
+    // accepted answers become known answers for `verify` and count as stars in `status`.
+    if let Ok(output) = &result
+        && aoc_cli::is_accepted(output)
+        && let Err(e) = Answers::record(day, "input", part, &answer)
+    {
+        eprintln!("Failed to record the accepted answer: {e}");
+    }
+
+    Some(result)
+}
+
+/// Asks a yes/no question on stdin, defaulting to no.
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = stdout().flush();
+
+    let mut line = String::new();
+    if stdin().read_line(&mut line).is_err() {
+        return false;
+    }
+
+    matches!(line.trim(), "y" | "Y" | "yes")
 }
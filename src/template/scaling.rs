@@ -0,0 +1,428 @@
+/// Empirical complexity: benches a part on generated inputs of doubling size and fits the timings against
+/// common complexity classes, to tell whether an optimisation changed the asymptotics or only the constant.
+///
+/// `scale <day> --part <part>` needs the day to register a generator, see `template::generate`. Sizes are
+/// the generator's `size`, e.g. the number of lines, so the classes describe the growth in that dimension.
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+use std::{env, process};
+
+use crate::template::Day;
+use crate::template::charts::format_nanos;
+use crate::template::context::Context;
+use crate::template::generate::{Generator, Rng};
+use crate::template::runner::{self, Outcome, PartResult};
+
+/// Sizes stop growing once a single run takes longer, as every size is benched for about a second.
+const MAX_RUN: Duration = Duration::from_millis(100);
+/// Fits need a few sizes to tell the classes apart.
+const MIN_SIZES: usize = 4;
+const PLOT_WIDTH: usize = 48;
+const PLOT_HEIGHT: usize = 12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Complexity {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+}
+
+impl Complexity {
+    const ALL: [Self; 6] = [
+        Self::Constant,
+        Self::Logarithmic,
+        Self::Linear,
+        Self::Linearithmic,
+        Self::Quadratic,
+        Self::Cubic,
+    ];
+
+    fn eval(self, n: f64) -> f64 {
+        // sizes below 2 would make the logarithms vanish.
+        let log = n.max(2.0).log2();
+        match self {
+            Self::Constant => 1.0,
+            Self::Logarithmic => log,
+            Self::Linear => n,
+            Self::Linearithmic => n * log,
+            Self::Quadratic => n * n,
+            Self::Cubic => n * n * n,
+        }
+    }
+
+    /// The term multiplied with the coefficient, e.g. `n log n`.
+    fn term(self) -> &'static str {
+        match self {
+            Self::Constant => "1",
+            Self::Logarithmic => "log n",
+            Self::Linear => "n",
+            Self::Linearithmic => "n log n",
+            Self::Quadratic => "n²",
+            Self::Cubic => "n³",
+        }
+    }
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "O({})", self.term())
+    }
+}
+
+/// `time ≈ coefficient · f(size)` for a complexity class `f`.
+#[derive(Clone, Copy, Debug)]
+pub struct Fit {
+    pub complexity: Complexity,
+    /// Nanoseconds per unit of the class, e.g. per `n²`.
+    pub coefficient: f64,
+    /// The root mean square of the residuals, relative to the timings.
+    pub error: f64,
+}
+
+impl Fit {
+    fn nanos(&self, size: f64) -> f64 {
+        self.coefficient * self.complexity.eval(size)
+    }
+}
+
+/// Fits every class to the `(size, nanos)` points, best fit first. The residuals are relative to the timings,
+/// so that small sizes weigh as much as large ones.
+pub fn fit(points: &[(usize, f64)]) -> Vec<Fit> {
+    let mut fits: Vec<Fit> = Complexity::ALL
+        .iter()
+        .map(|&complexity| {
+            // minimises Σ (1 - c·r)² with r = f(size) / time.
+            let ratios: Vec<f64> = points
+                .iter()
+                .map(|&(size, nanos)| complexity.eval(size as f64) / nanos.max(1.0))
+                .collect();
+            let coefficient =
+                ratios.iter().sum::<f64>() / ratios.iter().map(|r| r * r).sum::<f64>();
+            let error = (ratios
+                .iter()
+                .map(|r| (1.0 - coefficient * r).powi(2))
+                .sum::<f64>()
+                / ratios.len() as f64)
+                .sqrt();
+            Fit {
+                complexity,
+                coefficient,
+                error,
+            }
+        })
+        .collect();
+
+    fits.sort_by(|a, b| a.error.total_cmp(&b.error));
+    fits
+}
+
+/// Formats the coefficient of a fit, which is below a nanosecond for the higher complexity classes.
+fn format_coefficient(nanos: f64) -> String {
+    if nanos >= 1.0 {
+        format_nanos(nanos)
+    } else {
+        format!("{nanos:.2e}ns")
+    }
+}
+
+/// The slope of the timings on a log-log scale, i.e. the exponent `k` of `time ≈ c · size^k`.
+pub fn slope(points: &[(usize, f64)]) -> f64 {
+    let logs: Vec<(f64, f64)> = points
+        .iter()
+        .map(|&(size, nanos)| ((size as f64).ln(), nanos.max(1.0).ln()))
+        .collect();
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let covariance: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if variance == 0.0 {
+        0.0
+    } else {
+        covariance / variance
+    }
+}
+
+/// Plots the timings (`●`) and the fit (`·`) on log-log axes.
+pub fn plot(points: &[(usize, f64)], fit: &Fit) -> String {
+    let (min_size, max_size) = (points[0].0 as f64, points[points.len() - 1].0 as f64);
+    let (min_nanos, max_nanos) = points
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(min, max), &(_, nanos)| {
+            (min.min(nanos), max.max(nanos))
+        });
+
+    let scale = |value: f64, min: f64, max: f64, cells: usize| -> Option<usize> {
+        let (value, min, max) = (value.max(1.0).ln(), min.max(1.0).ln(), max.max(1.0).ln());
+        let position = if max > min {
+            (value - min) / (max - min)
+        } else {
+            0.5
+        };
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        (0.0..=1.0)
+            .contains(&position)
+            .then(|| (position * (cells - 1) as f64).round() as usize)
+    };
+
+    let mut grid = vec![vec![' '; PLOT_WIDTH]; PLOT_HEIGHT];
+    let curve = (0..PLOT_WIDTH).filter_map(|x| {
+        let size = min_size * (max_size / min_size).powf(x as f64 / (PLOT_WIDTH - 1) as f64);
+        Some((
+            x,
+            scale(fit.nanos(size), min_nanos, max_nanos, PLOT_HEIGHT)?,
+        ))
+    });
+    for (x, y) in curve {
+        grid[PLOT_HEIGHT - 1 - y][x] = '·';
+    }
+    for &(size, nanos) in points {
+        if let (Some(x), Some(y)) = (
+            scale(size as f64, min_size, max_size, PLOT_WIDTH),
+            scale(nanos, min_nanos, max_nanos, PLOT_HEIGHT),
+        ) {
+            grid[PLOT_HEIGHT - 1 - y][x] = '●';
+        }
+    }
+
+    let label_width = 9;
+    let mut plot = String::new();
+    for (y, row) in grid.iter().enumerate() {
+        let label = match y {
+            0 => format_nanos(max_nanos),
+            y if y == PLOT_HEIGHT - 1 => format_nanos(min_nanos),
+            _ => String::new(),
+        };
+        let axis = if label.is_empty() { '│' } else { '┤' };
+        let row: String = row.iter().collect();
+        plot.push_str(&format!("{label:>label_width$} {axis}{}\n", row.trim_end()));
+    }
+    plot.push_str(&format!(
+        "{:>label_width$} └{}\n",
+        "",
+        "─".repeat(PLOT_WIDTH)
+    ));
+    let (first, last) = (
+        points[0].0.to_string(),
+        points[points.len() - 1].0.to_string(),
+    );
+    plot.push_str(&format!(
+        "{:>label_width$}  {first}{last:>width$}\n",
+        "",
+        width = PLOT_WIDTH - first.len()
+    ));
+    plot
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Benches a part, see `runner::bench`. Errors if it did not answer the input.
+pub fn measure<R: PartResult>(func: impl Fn() -> R) -> Result<Duration, String> {
+    let timer = Instant::now();
+    let outcome = func().into_outcome();
+    let base_time = timer.elapsed();
+
+    match outcome {
+        Outcome::Solved(_) => Ok(runner::bench(|()| func(), (), &base_time).0),
+        Outcome::NotImplemented => Err("it is not implemented yet.".into()),
+        Outcome::Failed(e) => Err(format!("it failed: {e}")),
+    }
+}
+
+/// Benches a part of a day on an input, or `None` if the day has no such part. Built by `solution!`.
+pub type MeasureFn = dyn Fn(u8, &str, &Context) -> Option<Result<Duration, String>>;
+
+/// The arguments `scale` passes to a solution: `--scale --part <part> --size <size> --steps <steps> --seed <seed>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Request {
+    pub part: u8,
+    /// The smallest size, which doubles `steps - 1` times.
+    pub size: usize,
+    pub steps: u32,
+    pub seed: u64,
+}
+
+/// Returns the request if the solution was started by `scale`.
+pub fn request() -> Option<Request> {
+    let args: Vec<String> = env::args().collect();
+    if !args.iter().any(|arg| arg == "--scale") {
+        return None;
+    }
+
+    let value = |name: &str| {
+        let i = args.iter().position(|arg| arg == name)?;
+        args.get(i + 1)
+    };
+
+    let part = value("--part").and_then(|s| s.parse().ok());
+    let size = value("--size").and_then(|s| s.parse().ok());
+    let steps = value("--steps").and_then(|s| s.parse().ok());
+    let seed = value("--seed").and_then(|s| s.parse().ok());
+
+    match (part, size, steps, seed) {
+        (Some(part), Some(size), Some(steps), Some(seed)) => Some(Request {
+            part,
+            size,
+            steps,
+            seed,
+        }),
+        _ => {
+            eprintln!(
+                "`--scale` requires `--part <part>`, `--size <size>`, `--steps <steps>` and `--seed <seed>`."
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Benches a part on inputs of growing size, prints the best fit and exits. Called by `solution!` instead of
+/// running the parts.
+pub fn run(
+    day: Day,
+    request: Request,
+    generator: Option<&dyn Generator>,
+    ctx: &Context,
+    measure: &MeasureFn,
+) -> ! {
+    let Some(generator) = generator else {
+        eprintln!(
+            "Day {day} has no input generator. Register one with `solution!({}, generate = generate)`.",
+            day.into_inner()
+        );
+        process::exit(1);
+    };
+
+    println!("Part {} of day {day}, seed {}:", request.part, request.seed);
+    println!("{:>10} {:>12} {:>12}", "size", "input", "time");
+
+    let mut points = vec![];
+    for step in 0..request.steps {
+        let size = request.size.max(1) << step;
+        let input = generator.generate(size, &mut Rng::new(request.seed));
+
+        let nanos = match measure(request.part, &input, ctx) {
+            Some(Ok(duration)) => duration.as_nanos() as f64,
+            Some(Err(e)) => {
+                eprintln!(
+                    "Error: part {} did not answer the input of size {size}, {e}",
+                    request.part
+                );
+                process::exit(1);
+            }
+            None => {
+                eprintln!("Day {day} has no part {}.", request.part);
+                process::exit(1);
+            }
+        };
+
+        println!(
+            "{size:>10} {:>12} {:>12}",
+            format!("{}B", input.len()),
+            format_nanos(nanos)
+        );
+        points.push((size, nanos));
+
+        if nanos > MAX_RUN.as_nanos() as f64 {
+            println!("Stopped growing, a run took longer than {MAX_RUN:?}.");
+            break;
+        }
+    }
+
+    if points.len() < MIN_SIZES {
+        eprintln!(
+            "Error: fits need at least {MIN_SIZES} sizes, but only {} were benched. Pass a smaller `--size` or more `--steps`.",
+            points.len()
+        );
+        process::exit(1);
+    }
+
+    let fits = fit(&points);
+    let best = &fits[0];
+    println!();
+    print!("{}", plot(&points, best));
+    println!();
+    println!(
+        "Best fit: {} ≈ {} · {} (error {:.1}%), log-log slope {:.2}.",
+        best.complexity,
+        format_coefficient(best.coefficient),
+        best.complexity.term(),
+        best.error * 100.0,
+        slope(&points)
+    );
+    println!(
+        "Other fits: {}.",
+        fits[1..]
+            .iter()
+            .map(|fit| format!("{} {:.1}%", fit.complexity, fit.error * 100.0))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    process::exit(0);
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Complexity, fit, format_coefficient, plot, slope};
+
+    fn points(f: impl Fn(f64) -> f64) -> Vec<(usize, f64)> {
+        (0..8)
+            .map(|step| {
+                let size = 16 << step;
+                // some noise and a constant overhead, like real timings.
+                let noise = if step % 2 == 0 { 1.03 } else { 0.97 };
+                (size, (f(size as f64) + 200.0) * noise)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fits_complexity_classes() {
+        let cases = [
+            (Complexity::Constant, points(|_| 5_000.0)),
+            (Complexity::Linear, points(|n| 40.0 * n)),
+            (Complexity::Linearithmic, points(|n| 20.0 * n * n.log2())),
+            (Complexity::Quadratic, points(|n| 3.0 * n * n)),
+            (Complexity::Cubic, points(|n| 0.5 * n * n * n)),
+        ];
+        for (complexity, points) in cases {
+            let fits = fit(&points);
+            assert_eq!(fits[0].complexity, complexity);
+            assert!(fits[0].error < 0.1, "{:?}", fits[0]);
+        }
+    }
+
+    #[test]
+    fn formats_coefficients() {
+        assert_eq!(format_coefficient(44.0), "44.0ns");
+        assert_eq!(format_coefficient(1500.0), "1.5µs");
+        assert_eq!(format_coefficient(0.0352), "3.52e-2ns");
+    }
+
+    #[test]
+    fn measures_the_slope() {
+        assert!((slope(&points(|n| 3.0 * n * n)) - 2.0).abs() < 0.1);
+        assert!((slope(&points(|n| 40.0 * n)) - 1.0).abs() < 0.1);
+        assert!(slope(&points(|_| 5_000.0)).abs() < 0.1);
+    }
+
+    #[test]
+    fn plots_points_and_fit() {
+        let points = points(|n| 40.0 * n);
+        let plot = plot(&points, &fit(&points)[0]);
+        let lines: Vec<&str> = plot.lines().collect();
+
+        assert_eq!(lines.len(), super::PLOT_HEIGHT + 2);
+        assert_eq!(plot.matches('●').count(), points.len());
+        assert!(plot.contains('·'));
+        assert!(lines[0].contains("µs ┤"), "{plot}");
+        assert!(lines[super::PLOT_HEIGHT - 1].contains("ns ┤"), "{plot}");
+        assert!(lines[lines.len() - 1].trim().starts_with("16"));
+        assert!(lines[lines.len() - 1].trim().ends_with("2048"));
+    }
+}
@@ -0,0 +1,288 @@
+/// Encrypted storage of personal puzzle data, so it can be committed without publishing it.
+///
+/// `inputs seal` writes inputs, puzzle descriptions and answers into an AES-256 encrypted zip archive
+/// (`seal.archive`), using a key that never leaves the machine: the `AOC_SEAL_KEY` environment variable or
+/// the key file (`seal.key_file`). `inputs unseal` restores the files, and [`crate::template::read_file`]
+/// reads from the archive if a plain file is missing.
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::{env, process};
+
+use zip::result::ZipError;
+use zip::write::SimpleFileOptions;
+use zip::{AesMode, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::template::config;
+
+pub static KEY_ENV: &str = "AOC_SEAL_KEY";
+
+/// Data folders stored in the archive, with the extension of their files.
+static FOLDERS: [(&str, &str); 3] = [("inputs", "txt"), ("puzzles", "md"), ("answers", "toml")];
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+    Zip(ZipError),
+    MissingKey,
+    WrongKey,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Zip(e) => write!(f, "{e}"),
+            Error::MissingKey => write!(
+                f,
+                "no key found. Set `{KEY_ENV}` or copy the key file \"{}\" from the machine that sealed the archive.",
+                config::get().seal.key_file
+            ),
+            Error::WrongKey => write!(
+                f,
+                "the key does not match the one the archive was sealed with."
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<ZipError> for Error {
+    fn from(e: ZipError) -> Self {
+        match e {
+            ZipError::InvalidPassword => Error::WrongKey,
+            e => Error::Zip(e),
+        }
+    }
+}
+
+/// Contents of the archive, keyed by `folder/file`, e.g. `inputs/01.txt`.
+type Entries = BTreeMap<String, Vec<u8>>;
+
+/// Reads the key from the environment or the key file.
+fn key() -> Result<String, Error> {
+    if let Ok(key) = env::var(KEY_ENV)
+        && !key.trim().is_empty()
+    {
+        return Ok(key.trim().to_string());
+    }
+
+    match fs::read_to_string(&config::get().seal.key_file) {
+        Ok(key) if !key.trim().is_empty() => Ok(key.trim().to_string()),
+        Ok(_) => Err(Error::MissingKey),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(Error::MissingKey),
+        Err(e) => Err(Error::IO(e)),
+    }
+}
+
+/// Creates a random key and writes it to the key file.
+fn create_key() -> Result<String, Error> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes).map_err(|e| Error::IO(io::Error::other(e.to_string())))?;
+    let key: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+
+    fs::write(&config::get().seal.key_file, format!("{key}\n"))?;
+    Ok(key)
+}
+
+fn local_path(name: &str) -> Option<PathBuf> {
+    let (folder, file) = name.split_once('/')?;
+    Some(Path::new(&config::get().data_dir(folder)).join(file))
+}
+
+fn archive_path() -> &'static Path {
+    Path::new(&config::get().seal.archive)
+}
+
+/// Reads all entries of an archive. Returns no entries if there is no archive.
+fn read_archive(path: &Path, key: &str) -> Result<Entries, Error> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Entries::new()),
+        Err(e) => return Err(Error::IO(e)),
+    };
+
+    let mut archive = ZipArchive::new(file)?;
+    let mut entries = Entries::new();
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index_decrypt(i, key.as_bytes())?;
+        let mut content = vec![];
+        entry.read_to_end(&mut content)?;
+        entries.insert(entry.name().to_string(), content);
+    }
+
+    Ok(entries)
+}
+
+/// Reads the inputs, puzzles and answers in the data folders.
+fn read_local() -> Result<Entries, Error> {
+    let mut entries = Entries::new();
+
+    for (folder, extension) in FOLDERS {
+        let Ok(dir) = fs::read_dir(config::get().data_dir(folder)) else {
+            continue;
+        };
+
+        for entry in dir {
+            let path = entry?.path();
+            if path.extension().is_none_or(|e| e != extension) {
+                continue;
+            }
+            // empty files are placeholders created by `scaffold`.
+            let content = fs::read(&path)?;
+            if content.is_empty() {
+                continue;
+            }
+            let file_name = path.file_name().unwrap().to_string_lossy();
+            entries.insert(format!("{folder}/{file_name}"), content);
+        }
+    }
+
+    Ok(entries)
+}
+
+fn write_archive(path: &Path, entries: &Entries, key: &str) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .with_aes_encryption(AesMode::Aes256, key);
+
+    let mut archive = ZipWriter::new(File::create(path)?);
+    for (name, content) in entries {
+        archive.start_file(name, options)?;
+        archive.write_all(content)?;
+    }
+    archive.finish()?;
+
+    Ok(())
+}
+
+/// The outcome of sealing: the names of the entries that were added or changed.
+pub struct Sealed {
+    pub changed: Vec<String>,
+    pub total: usize,
+    /// Set if the key file was created by this call.
+    pub created_key: bool,
+}
+
+/// Adds the local data files to the archive. Entries of other machines that are missing locally are kept.
+/// The archive is only rewritten if something changed, to keep its history free of noise.
+pub fn seal() -> Result<Sealed, Error> {
+    let (key, created_key) = match key() {
+        Ok(key) => (key, false),
+        Err(Error::MissingKey) if !archive_path().exists() => (create_key()?, true),
+        Err(e) => return Err(e),
+    };
+
+    let mut entries = read_archive(archive_path(), &key)?;
+
+    let mut changed = vec![];
+    for (name, content) in read_local()? {
+        if entries.get(&name) != Some(&content) {
+            entries.insert(name.clone(), content);
+            changed.push(name);
+        }
+    }
+
+    if !changed.is_empty() {
+        write_archive(archive_path(), &entries, &key)?;
+    }
+
+    Ok(Sealed {
+        changed,
+        total: entries.len(),
+        created_key,
+    })
+}
+
+/// Writes the entries of the archive to the data folders. Existing files are only replaced with `overwrite`,
+/// or if they are empty.
+/// Returns the names of the written files.
+pub fn unseal(overwrite: bool) -> Result<Vec<String>, Error> {
+    let entries = read_archive(archive_path(), &key()?)?;
+    let mut written = vec![];
+
+    for (name, content) in entries {
+        let Some(path) = local_path(&name) else {
+            continue;
+        };
+        if !overwrite && fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, content)?;
+        written.push(path.to_string_lossy().to_string());
+    }
+
+    Ok(written)
+}
+
+/// Reads a single file of a data folder from the archive, e.g. `read("inputs", "01.txt")`.
+/// Returns `None` if the archive does not contain the file, and exits if it cannot be decrypted.
+pub fn read(folder: &str, file_name: &str) -> Option<String> {
+    let name = format!("{folder}/{file_name}");
+    let mut archive = ZipArchive::new(File::open(archive_path()).ok()?).ok()?;
+
+    // names are not encrypted, so the key is only needed if the file is in the archive.
+    archive.index_for_name(&name)?;
+
+    let content = key().and_then(|key| {
+        let mut content = String::new();
+        archive
+            .by_name_decrypt(&name, key.as_bytes())?
+            .read_to_string(&mut content)?;
+        Ok(content)
+    });
+
+    match content {
+        Ok(content) => Some(content),
+        Err(e) => {
+            eprintln!(
+                "Could not read \"{name}\" from \"{}\": {e}",
+                archive_path().display()
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::env;
+
+    use super::{Entries, Error, read_archive, write_archive};
+
+    #[test]
+    fn roundtrips_archives() {
+        let path = env::temp_dir().join(format!("aoc-sealed-{}.zip", std::process::id()));
+        let entries = Entries::from([
+            ("inputs/01.txt".into(), b"1\n2\n3\n".to_vec()),
+            ("puzzles/01.md".into(), b"## --- Day 1 ---".to_vec()),
+        ]);
+
+        write_archive(&path, &entries, "secret").unwrap();
+        let raw = std::fs::read(&path).unwrap();
+
+        assert!(!raw.windows(6).any(|w| w == b"1\n2\n3\n"));
+        assert_eq!(read_archive(&path, "secret").unwrap(), entries);
+        assert!(matches!(read_archive(&path, "wrong"), Err(Error::WrongKey)));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(read_archive(&path, "secret").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+/// Gathers the progress of every day from the files of the project.
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::Timings;
+use crate::template::{Day, config};
+
+/// The progress of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayStatus {
+    pub day: Day,
+    /// `src/bin/NN.rs` exists.
+    pub scaffolded: bool,
+    /// The real input was downloaded.
+    pub input: bool,
+    /// Number of parts with a known example answer.
+    pub example_answers: u8,
+    /// Whether `cargo test` passes for the day. `None` if tests were not run.
+    pub tests: Option<bool>,
+    /// Number of parts whose answer was accepted.
+    pub stars: u8,
+    /// Both parts have stored timings.
+    pub benched: bool,
+}
+
+impl DayStatus {
+    /// Whether any work has been done on this day.
+    pub fn is_started(&self) -> bool {
+        self.scaffolded || self.input || self.stars > 0
+    }
+
+    /// Whether the star of a part was earned.
+    pub fn has_star(&self, part: u8) -> bool {
+        self.stars >= part
+    }
+}
+
+/// Collects the status of a day. Runs the day's tests if `run_tests` is set and the day is scaffolded.
+pub fn collect(day: Day, timings: &Timings, run_tests: bool) -> DayStatus {
+    let config = config::get();
+    let scaffolded = Path::new(&get_path_for_bin(day)).exists();
+
+    // a broken answers file should not hide the rest of the status.
+    let answers = Answers::read(day).unwrap_or_default();
+    #[allow(clippy::cast_possible_truncation)]
+    let count_answers = |input: &str| {
+        (1..=2)
+            .filter(|part| answers.get(input, *part).is_some())
+            .count() as u8
+    };
+
+    // the puzzle description lists accepted answers once it is downloaded again.
+    let accepted = fs::read_to_string(format!("{}/{day}.md", config.data_dir("puzzles")))
+        .map(|puzzle| stars_in_puzzle(&puzzle))
+        .unwrap_or_default();
+
+    DayStatus {
+        day,
+        scaffolded,
+        input: Path::new(&format!("{}/{day}.txt", config.data_dir("inputs"))).exists(),
+        example_answers: count_answers("example"),
+        tests: (run_tests && scaffolded).then(|| run_day_tests(day)),
+        stars: accepted.max(count_answers("input")),
+        benched: timings.is_day_complete(&day),
+    }
+}
+
+/// Counts the answers shown in a puzzle description downloaded by aoc-cli.
+fn stars_in_puzzle(puzzle: &str) -> u8 {
+    #[allow(clippy::cast_possible_truncation)]
+    let count = puzzle.matches("Your puzzle answer was").count().min(2) as u8;
+    count
+}
+
+fn run_day_tests(day: Day) -> bool {
+    Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &day.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Star counts per part.
+pub fn stars_per_part(statuses: &[DayStatus]) -> [usize; 2] {
+    [1, 2].map(|part| statuses.iter().filter(|s| s.has_star(part)).count())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DayStatus, stars_in_puzzle, stars_per_part};
+    use crate::day;
+
+    #[test]
+    fn counts_stars_in_puzzle() {
+        let puzzle = "## --- Day 1 ---\nYour puzzle answer was `11`.\n\n## --- Part Two ---\n";
+        assert_eq!(stars_in_puzzle(puzzle), 1);
+        assert_eq!(stars_in_puzzle(&puzzle.repeat(3)), 2);
+        assert_eq!(stars_in_puzzle(""), 0);
+    }
+
+    #[test]
+    fn counts_stars_per_part() {
+        let status = |stars: u8| DayStatus {
+            day: day!(1),
+            scaffolded: true,
+            input: true,
+            example_answers: 2,
+            tests: None,
+            stars,
+            benched: false,
+        };
+        let statuses = [status(2), status(1), status(0)];
+
+        assert_eq!(stars_per_part(&statuses), [2, 1]);
+    }
+}
@@ -0,0 +1,180 @@
+/// Solution templates used by `scaffold`.
+///
+/// Built-in templates are compiled into the binary. Templates in the user template directory,
+/// e.g. `templates/dp.txt`, are selected by their file name and take precedence over built-ins of the same name.
+use std::fs;
+use std::path::Path;
+
+use crate::template::Day;
+
+macro_rules! builtin {
+    ($name:literal) => {
+        (
+            $name,
+            include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/templates/",
+                $name,
+                ".txt"
+            )),
+        )
+    };
+}
+
+/// Name and source of the built-in templates.
+static BUILTINS: [(&str, &str); 4] = [
+    builtin!("plain"),
+    builtin!("grid"),
+    builtin!("graph"),
+    builtin!("parse-once"),
+];
+
+/// Values substituted for placeholders like `%DAY_NUMBER%`.
+pub struct Placeholders {
+    pub day: Day,
+    pub year: Option<u16>,
+    /// The title of the puzzle, e.g. `Trebuchet?!`.
+    pub title: Option<String>,
+    /// Known answers of the example, by part.
+    pub example_answers: [Option<String>; 2],
+}
+
+impl Placeholders {
+    fn values(&self) -> Vec<(&'static str, String)> {
+        let day = self.day.into_inner();
+
+        let title = match &self.title {
+            Some(title) => format!("Day {day}: {title}"),
+            None => format!("Day {day}"),
+        };
+
+        let url = match self.year {
+            Some(year) => format!("https://adventofcode.com/{year}/day/{day}"),
+            None => "https://adventofcode.com".into(),
+        };
+
+        // answers that are not numbers cannot be compared with the default `Option<u64>` return type.
+        let expected = |answer: &Option<String>| match answer {
+            Some(answer) if answer.parse::<u64>().is_ok() => format!("Some({answer})"),
+            _ => "None".into(),
+        };
+
+        vec![
+            ("%DAY_NUMBER%", day.to_string()),
+            ("%DAY%", self.day.to_string()),
+            (
+                "%YEAR%",
+                self.year.map(|year| year.to_string()).unwrap_or_default(),
+            ),
+            ("%TITLE%", title),
+            ("%PUZZLE_URL%", url),
+            ("%EXAMPLE_PART_1%", expected(&self.example_answers[0])),
+            ("%EXAMPLE_PART_2%", expected(&self.example_answers[1])),
+        ]
+    }
+}
+
+/// Returns the source of a template, looking in `dir` before the built-ins.
+pub fn find(name: &str, dir: &str) -> Option<String> {
+    let path = Path::new(dir).join(format!("{name}.txt"));
+    if let Ok(source) = fs::read_to_string(path) {
+        return Some(source);
+    }
+
+    BUILTINS
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, source)| (*source).to_string())
+}
+
+/// Names of all templates, built-ins first.
+pub fn available(dir: &str) -> Vec<String> {
+    let mut names: Vec<String> = BUILTINS.iter().map(|(name, _)| name.to_string()).collect();
+
+    let mut user: Vec<String> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            Some(path.file_stem()?.to_string_lossy().to_string())
+        })
+        .filter(|name| !names.contains(name))
+        .collect();
+
+    user.sort();
+    names.append(&mut user);
+    names
+}
+
+/// Replaces all placeholders in a template.
+pub fn render(source: &str, placeholders: &Placeholders) -> String {
+    placeholders
+        .values()
+        .into_iter()
+        .fold(source.to_string(), |out, (placeholder, value)| {
+            out.replace(placeholder, &value)
+        })
+}
+
+/// Reads the title from a puzzle description downloaded by aoc-cli, e.g. `## --- Day 1: Trebuchet?! ---`.
+pub fn puzzle_title(puzzle: &str) -> Option<String> {
+    let heading = puzzle.lines().find(|line| line.contains("--- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim_end_matches('-').trim().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BUILTINS, Placeholders, available, find, puzzle_title, render};
+    use crate::day;
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = Placeholders {
+            day: day!(5),
+            year: Some(2024),
+            title: Some("Print Queue".into()),
+            example_answers: [Some("143".into()), Some("ABC".into())],
+        };
+
+        let source = "// %TITLE% - %PUZZLE_URL%\nsolution!(%DAY_NUMBER%); // %DAY% %YEAR%\n%EXAMPLE_PART_1% %EXAMPLE_PART_2%";
+        assert_eq!(
+            render(source, &placeholders),
+            "// Day 5: Print Queue - https://adventofcode.com/2024/day/5\nsolution!(5); // 05 2024\nSome(143) None"
+        );
+    }
+
+    #[test]
+    fn renders_missing_placeholders() {
+        let placeholders = Placeholders {
+            day: day!(5),
+            year: None,
+            title: None,
+            example_answers: [None, None],
+        };
+
+        assert_eq!(
+            render("// %TITLE% - %PUZZLE_URL%", &placeholders),
+            "// Day 5 - https://adventofcode.com"
+        );
+    }
+
+    #[test]
+    fn finds_builtin_templates() {
+        assert!(find("grid", "does-not-exist").unwrap().contains("mygrid"));
+        assert!(find("unknown", "does-not-exist").is_none());
+        assert_eq!(available("does-not-exist").len(), BUILTINS.len());
+    }
+
+    #[test]
+    fn reads_puzzle_title() {
+        let puzzle = "\n## --- Day 1: Trebuchet?! ---\nSomething is wrong";
+        assert_eq!(puzzle_title(puzzle), Some("Trebuchet?!".into()));
+        assert_eq!(puzzle_title("no title"), None);
+    }
+}
@@ -1,9 +1,43 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::environment::Environment;
+use crate::template::{Day, config};
+
+/// Represents the outcome of a single solution part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part produced an answer.
+    Solved,
+    /// The part returned `None` or was not run.
+    #[default]
+    NotImplemented,
+    /// The part returned an error.
+    Failed,
+}
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+impl PartStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::NotImplemented => "not_implemented",
+            PartStatus::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(PartStatus::Solved),
+            "not_implemented" => Ok(PartStatus::NotImplemented),
+            "failed" => Ok(PartStatus::Failed),
+            _ => Err(format!("unknown part status `{s}`.")),
+        }
+    }
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,7 +45,12 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_status: PartStatus,
+    pub part_2_status: PartStatus,
     pub total_nanos: f64,
+    /// The index of the machine and toolchain the day was benched with in [`Timings::environments`].
+    /// Unknown for timings stored before it was tracked.
+    pub environment: Option<usize>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -19,19 +58,21 @@ pub struct Timing {
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    /// The environments the days were benched with, each stored once.
+    pub environments: Vec<Environment>,
 }
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(&config::get().data.timings)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+        fs::read_to_string(&config::get().data.timings)
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -39,20 +80,62 @@ impl Timings {
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
     pub fn merge(&self, new: &Self) -> Self {
-        let mut data: Vec<Timing> = vec![];
+        let mut merged = Timings::default();
 
         for timing in &new.data {
-            data.push(timing.clone());
+            merged.push(timing, new);
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
-                data.push(timing.clone());
+            if !merged.data.iter().any(|t| t.day == timing.day) {
+                merged.push(timing, self);
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        merged.data.sort_unstable_by_key(|t| t.day);
+        merged
+    }
+
+    /// Adds a timing of `source`, along with its environment if that is not stored yet.
+    fn push(&mut self, timing: &Timing, source: &Self) {
+        let environment = timing
+            .environment
+            .and_then(|i| source.environments.get(i))
+            .map(|environment| {
+                self.environments
+                    .iter()
+                    .position(|e| e == environment)
+                    .unwrap_or_else(|| {
+                        self.environments.push(environment.clone());
+                        self.environments.len() - 1
+                    })
+            });
+
+        self.data.push(Timing {
+            environment,
+            ..timing.clone()
+        });
+    }
+
+    /// Set the environment of all timings.
+    pub fn with_environment(mut self, environment: &Environment) -> Self {
+        for timing in &mut self.data {
+            timing.environment = Some(0);
+        }
+        self.environments = vec![environment.clone()];
+        self
+    }
+
+    /// Timings benched on another machine or toolchain, or before environments were tracked.
+    pub fn benched_elsewhere(&self, environment: &Environment) -> Vec<&Timing> {
+        self.data
+            .iter()
+            .filter(|t| {
+                !t.environment
+                    .and_then(|i| self.environments.get(i))
+                    .is_some_and(|e| e.same_machine(environment))
+            })
+            .collect()
     }
 
     /// Sum up total duration of timings as millis.
@@ -60,10 +143,35 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    pub fn is_day_complete(&self, day: &Day) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.day == *day && t.part_1.is_some() && t.part_2.is_some())
+    }
+}
+
+impl Timing {
+    /// Returns the benched duration of a part in nanoseconds.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        let timing = if part == 1 {
+            &self.part_1
+        } else {
+            &self.part_2
+        };
+        timing.as_deref().and_then(parse_duration)
+    }
+}
+
+/// Parses a duration formatted with `{:?}`, e.g. `74.13ms`, to nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    let parse_to_float = |s: &str, postfix: &str| s.split(postfix).next()?.parse::<f64>().ok();
+
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
     }
 }
 
@@ -78,6 +186,13 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        if !value.environments.is_empty() {
+            map.insert(
+                "environments".into(),
+                JsonValue::Array(value.environments.iter().map(JsonValue::from).collect()),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -88,20 +203,41 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
-        Ok(Timings {
-            data: json_data
+        let environments = match json.get("environments") {
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.environments` to be an array.")?
                 .iter()
-                .map(Timing::try_from)
+                .map(Environment::try_from)
                 .collect::<Result<_, _>>()?,
-        })
+            None => vec![],
+        };
+
+        let data: Vec<Timing> = json_data
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        if data
+            .iter()
+            .any(|t| t.environment.is_some_and(|i| i >= environments.len()))
+        {
+            return Err(
+                "expected timing.environment to be an index of `json.environments`.".into(),
+            );
+        }
+
+        Ok(Timings { data, environments })
     }
 }
 
@@ -133,6 +269,19 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "part_1_status".into(),
+            JsonValue::String(value.part_1_status.as_str().into()),
+        );
+        map.insert(
+            "part_2_status".into(),
+            JsonValue::String(value.part_2_status.as_str().into()),
+        );
+
+        if let Some(environment) = value.environment {
+            map.insert("environment".into(), JsonValue::Number(environment as f64));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,11 +315,35 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: timings stored before statuses were tracked only know whether a part was benched.
+        let parse_status = |key: &str, timing: Option<&String>| match json.get(key) {
+            Some(v) => v
+                .get::<String>()
+                .ok_or(format!("Expected timing.{key} to be a string."))
+                .and_then(|s| PartStatus::from_str(s)),
+            None if timing.is_some() => Ok(PartStatus::Solved),
+            None => Ok(PartStatus::NotImplemented),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let environment = match json.get("environment") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<f64>()
+                    .map(|i| *i as usize)
+                    .ok_or("Expected timing.environment to be null or a number.")?,
+            ),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_status: parse_status("part_1_status", part_1)?,
+            part_2_status: parse_status("part_2_status", part_2)?,
             total_nanos,
+            environment,
         })
     }
 }
@@ -181,7 +354,7 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartStatus, Timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -190,26 +363,39 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
                     total_nanos: 3e+10,
+                    environment: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
                     total_nanos: 7e+10,
+                    environment: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::NotImplemented,
                     total_nanos: 4e+10,
+                    environment: None,
                 },
             ],
+            environments: vec![],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{PartStatus, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -223,6 +409,33 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_part_statuses() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "part_1_status": "solved", "part_2_status": "failed", "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_status, PartStatus::Solved);
+            assert_eq!(timing.part_2_status, PartStatus::Failed);
+        }
+
+        #[test]
+        fn infers_missing_part_statuses() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_status, PartStatus::Solved);
+            assert_eq!(timing.part_2_status, PartStatus::NotImplemented);
+        }
+
+        #[test]
+        fn parses_part_durations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.5ms", "part_2": "20.1µs", "total_nanos": 1520100 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_nanos(1), Some(1_500_000_f64));
+            assert_eq!(timing.part_nanos(2).map(f64::round), Some(20_100_f64));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -271,7 +484,7 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartStatus, Timing, Timings},
         };
 
         #[test]
@@ -281,8 +494,12 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
                     total_nanos: 3_000_000_000_f64,
+                    environment: None,
                 }],
+                environments: vec![],
             };
 
             assert_eq!(timings.is_day_complete(&day!(1)), true);
@@ -295,8 +512,12 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::NotImplemented,
                     total_nanos: 1_000_000_000_f64,
+                    environment: None,
                 }],
+                environments: vec![],
             };
 
             assert_eq!(timings.is_day_complete(&day!(1)), false);
@@ -309,19 +530,103 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_status: PartStatus::NotImplemented,
+                    part_2_status: PartStatus::NotImplemented,
                     total_nanos: 0.0,
+                    environment: None,
                 }],
+                environments: vec![],
             };
 
             assert_eq!(timings.is_day_complete(&day!(1)), false);
         }
     }
 
-    mod merge {
+    mod environment {
         use crate::{
             day,
+            template::environment::Environment,
             template::timings::{Timing, Timings},
         };
+        use tinyjson::JsonValue;
+
+        use super::get_mock_timings;
+
+        fn get_mock_environment(cpu: &str) -> Environment {
+            Environment {
+                cpu: cpu.into(),
+                threads: 8,
+                governor: None,
+                kernel: "Darwin 24.1.0".into(),
+                rustc: "rustc 1.85.0".into(),
+                target_cpu: Some("native".into()),
+                target_features: vec!["neon".into()],
+                profile: "release".into(),
+            }
+        }
+
+        #[test]
+        fn stores_environments() {
+            let environment = get_mock_environment("Apple M1");
+            let timings = get_mock_timings().with_environment(&environment);
+            let json = JsonValue::from(timings).stringify().unwrap();
+
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.environments, vec![environment]);
+            assert!(timings.data.iter().all(|t| t.environment == Some(0)));
+        }
+
+        #[test]
+        fn rejects_unknown_environments() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "environment": 0 }] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
+        }
+
+        #[test]
+        fn handles_missing_environments() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].environment, None);
+        }
+
+        #[test]
+        fn finds_other_environments() {
+            let here = get_mock_environment("Apple M1");
+            let there = get_mock_environment("Apple M3");
+
+            let stored = get_mock_timings().with_environment(&there);
+            let new = Timings {
+                data: vec![Timing {
+                    environment: None,
+                    ..get_mock_timings().data[0].clone()
+                }],
+                environments: vec![],
+            }
+            .with_environment(&here);
+            let merged = stored.merge(&new);
+            assert_eq!(merged.environments.len(), 2);
+
+            let days: Vec<_> = merged
+                .benched_elsewhere(&here)
+                .iter()
+                .map(|t| t.day)
+                .collect();
+            assert_eq!(days, vec![day!(2), day!(4)]);
+            assert_eq!(get_mock_timings().benched_elsewhere(&here).len(), 3);
+
+            let tuned = Environment {
+                governor: Some("performance".into()),
+                ..here.clone()
+            };
+            assert_eq!(merged.benched_elsewhere(&tuned).len(), 2);
+        }
+    }
+
+    mod merge {
+        use crate::{
+            day,
+            template::timings::{PartStatus, Timing, Timings},
+        };
 
         use super::get_mock_timings;
 
@@ -333,8 +638,12 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_status: PartStatus::NotImplemented,
+                    part_2_status: PartStatus::NotImplemented,
                     total_nanos: 0_f64,
+                    environment: None,
                 }],
+                environments: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -353,8 +662,12 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_status: PartStatus::NotImplemented,
+                    part_2_status: PartStatus::NotImplemented,
                     total_nanos: 0_f64,
+                    environment: None,
                 }],
+                environments: vec![],
             };
             let merged = timings.merge(&other);
 
@@ -365,6 +678,15 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn parses_part_durations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.5ms", "part_2": "20.1µs", "total_nanos": 1520100 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_nanos(1), Some(1_500_000_f64));
+            assert_eq!(timing.part_nanos(2).map(f64::round), Some(20_100_f64));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -0,0 +1,216 @@
+/// Compares the results of solution parts with their known answers.
+use std::time::Duration;
+
+use crate::template::Day;
+use crate::template::answers::Answers;
+use crate::template::junit::{self, CaseOutcome, TestCase, TestSuite};
+use crate::template::render::PartReport;
+use crate::template::timings::PartStatus;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The answer matches the known answer.
+    Passed,
+    Wrong {
+        expected: String,
+        actual: String,
+    },
+    /// The part returned an error or did not report a result. Holds the error and its sources.
+    Failed(Vec<String>),
+    NotImplemented,
+    /// The part produced an answer, but there is no known answer to compare it to.
+    Unknown(String),
+    /// The input file does not exist.
+    MissingInput,
+}
+
+/// The verdict for a single day, part and input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verification {
+    pub day: Day,
+    pub part: u8,
+    /// The input kind, see [`crate::template::answers::INPUT_KINDS`].
+    pub input: &'static str,
+    pub verdict: Verdict,
+    pub duration: Duration,
+}
+
+impl Verification {
+    /// Verifies the report of a part, or the lack of one, against the expected answer.
+    pub fn new(
+        day: Day,
+        part: u8,
+        input: &'static str,
+        report: Option<&PartReport>,
+        expected: Option<&str>,
+    ) -> Self {
+        let Some(report) = report else {
+            return Self {
+                day,
+                part,
+                input,
+                verdict: Verdict::Failed(vec!["the solution did not report a result.".into()]),
+                duration: Duration::ZERO,
+            };
+        };
+
+        let verdict = match (report.status, &report.answer, expected) {
+            (PartStatus::Solved, Some(answer), Some(expected)) => {
+                if Answers::matches(expected, answer) {
+                    Verdict::Passed
+                } else {
+                    Verdict::Wrong {
+                        expected: expected.to_string(),
+                        actual: answer.clone(),
+                    }
+                }
+            }
+            (PartStatus::Solved, Some(answer), None) => Verdict::Unknown(answer.clone()),
+            (PartStatus::Failed, _, _) => Verdict::Failed(report.errors.clone()),
+            _ => Verdict::NotImplemented,
+        };
+
+        Self {
+            day,
+            part,
+            input,
+            verdict,
+            duration: report.duration,
+        }
+    }
+
+    pub fn missing_input(day: Day, part: u8, input: &'static str) -> Self {
+        Self {
+            day,
+            part,
+            input,
+            verdict: Verdict::MissingInput,
+            duration: Duration::ZERO,
+        }
+    }
+
+    /// Whether this verification should fail a run.
+    pub fn is_failure(&self) -> bool {
+        matches!(self.verdict, Verdict::Wrong { .. } | Verdict::Failed(_))
+    }
+
+    fn test_case(&self) -> TestCase {
+        let outcome = match &self.verdict {
+            Verdict::Passed => CaseOutcome::Passed,
+            Verdict::Wrong { expected, actual } => CaseOutcome::Failure {
+                message: format!("expected {expected}, got {}", actual.replace('\n', " ")),
+                details: format!("expected: {expected}\nactual: {actual}"),
+            },
+            Verdict::Failed(errors) => CaseOutcome::Error {
+                message: errors.first().cloned().unwrap_or_default(),
+                details: errors.join("\ncaused by: "),
+            },
+            Verdict::NotImplemented => CaseOutcome::Skipped {
+                message: "not implemented".into(),
+            },
+            Verdict::Unknown(answer) => CaseOutcome::Skipped {
+                message: format!("no known answer, got {}", answer.replace('\n', " ")),
+            },
+            Verdict::MissingInput => CaseOutcome::Skipped {
+                message: "missing input file".into(),
+            },
+        };
+
+        TestCase {
+            name: format!("Part {} ({})", self.part, self.input),
+            classname: format!("day{}", self.day),
+            time: self.duration,
+            outcome,
+        }
+    }
+}
+
+/// Renders verifications as a JUnit report with one test suite per day.
+pub fn to_junit(verifications: &[Verification]) -> String {
+    let mut suites: Vec<TestSuite> = vec![];
+
+    for verification in verifications {
+        let name = format!("Day {}", verification.day);
+        match suites.last_mut() {
+            Some(suite) if suite.name == name => suite.cases.push(verification.test_case()),
+            _ => suites.push(TestSuite {
+                name,
+                cases: vec![verification.test_case()],
+            }),
+        }
+    }
+
+    junit::to_xml("advent_of_code", &suites)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Verdict, Verification, to_junit};
+    use crate::day;
+    use crate::template::render::PartReport;
+    use crate::template::timings::PartStatus;
+
+    fn report(status: PartStatus, answer: Option<&str>, errors: &[&str]) -> PartReport {
+        PartReport {
+            day: day!(1),
+            part: 1,
+            status,
+            answer: answer.map(String::from),
+            errors: errors.iter().map(|e| e.to_string()).collect(),
+            records: vec![],
+            duration: Duration::from_micros(5),
+            samples: 1,
+        }
+    }
+
+    #[test]
+    fn verifies_reports() {
+        let verdict = |report: Option<&PartReport>, expected: Option<&str>| {
+            Verification::new(day!(1), 1, "input", report, expected).verdict
+        };
+
+        let solved = report(PartStatus::Solved, Some("42"), &[]);
+        assert_eq!(verdict(Some(&solved), Some("42")), Verdict::Passed);
+        assert_eq!(
+            verdict(Some(&solved), Some("41")),
+            Verdict::Wrong {
+                expected: "41".into(),
+                actual: "42".into()
+            }
+        );
+        assert_eq!(verdict(Some(&solved), None), Verdict::Unknown("42".into()));
+
+        let failed = report(PartStatus::Failed, None, &["oops"]);
+        assert_eq!(
+            verdict(Some(&failed), Some("42")),
+            Verdict::Failed(vec!["oops".into()])
+        );
+
+        let missing = report(PartStatus::NotImplemented, None, &[]);
+        assert_eq!(verdict(Some(&missing), Some("42")), Verdict::NotImplemented);
+
+        assert!(matches!(verdict(None, Some("42")), Verdict::Failed(_)));
+    }
+
+    #[test]
+    fn writes_one_suite_per_day() {
+        let solved = report(PartStatus::Solved, Some("42"), &[]);
+        let verifications = [
+            Verification::new(day!(1), 1, "input", Some(&solved), Some("41")),
+            Verification::new(day!(1), 1, "example", Some(&solved), Some("42")),
+            Verification::missing_input(day!(2), 1, "input"),
+        ];
+
+        let xml = to_junit(&verifications);
+        assert!(xml.contains("<testsuite name=\"Day 01\" tests=\"2\" failures=\"1\""));
+        assert!(xml.contains(
+            "<testsuite name=\"Day 02\" tests=\"1\" failures=\"0\" errors=\"0\" skipped=\"1\""
+        ));
+        assert!(xml.contains("<failure message=\"expected 41, got 42\""));
+        assert!(xml.contains("name=\"Part 1 (example)\" classname=\"day01\""));
+    }
+}
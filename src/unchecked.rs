@@ -0,0 +1,162 @@
+/// Unchecked accesses for hot loops that rely on guarantees of the puzzle input, e.g. that no splitter
+/// sits on an edge.
+///
+/// With the `checked` feature, every access is bounds-checked and panics instead, so that tests, `diff` and
+/// Miri report broken assumptions instead of reading out of bounds:
+/// ```sh
+/// cargo test --bin 07 --features checked
+/// ```
+/// Builds without the feature, e.g. `solve --release` and `time`, use the unchecked accesses.
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+/// Indexing without bounds checks, see the module docs.
+pub trait Unchecked<T> {
+    /// # Safety
+    ///
+    /// `index` must be in bounds, unless the `checked` feature is enabled.
+    unsafe fn at(&self, index: usize) -> &T;
+
+    /// # Safety
+    ///
+    /// `index` must be in bounds, unless the `checked` feature is enabled.
+    unsafe fn at_mut(&mut self, index: usize) -> &mut T;
+}
+
+impl<T> Unchecked<T> for [T] {
+    #[inline(always)]
+    unsafe fn at(&self, index: usize) -> &T {
+        if cfg!(feature = "checked") {
+            &self[index]
+        } else {
+            unsafe { self.get_unchecked(index) }
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn at_mut(&mut self, index: usize) -> &mut T {
+        if cfg!(feature = "checked") {
+            &mut self[index]
+        } else {
+            unsafe { self.get_unchecked_mut(index) }
+        }
+    }
+}
+
+/// A raw pointer into a slice. Moving it is always allowed, reading it must stay in bounds of the slice.
+pub struct Ptr<'a, T> {
+    ptr: *const T,
+    start: *const T,
+    len: usize,
+    slice: PhantomData<&'a [T]>,
+}
+
+impl<'a, T> Ptr<'a, T> {
+    /// A pointer to the first element of `slice`.
+    pub fn new(slice: &'a [T]) -> Self {
+        Self {
+            ptr: slice.as_ptr(),
+            start: slice.as_ptr(),
+            len: slice.len(),
+            slice: PhantomData,
+        }
+    }
+
+    /// The pointer one past the last element.
+    pub fn end(&self) -> Self {
+        Self {
+            ptr: self.start.wrapping_add(self.len),
+            ..*self
+        }
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn wrapping_add(self, count: usize) -> Self {
+        Self {
+            ptr: self.ptr.wrapping_add(count),
+            ..self
+        }
+    }
+
+    /// The number of elements between the start of the slice and the pointer.
+    #[inline(always)]
+    pub fn offset(&self) -> usize {
+        (self.ptr as usize).wrapping_sub(self.start as usize) / size_of::<T>().max(1)
+    }
+
+    /// # Safety
+    ///
+    /// The pointer must be in bounds of the slice, unless the `checked` feature is enabled.
+    #[inline(always)]
+    pub unsafe fn read(&self) -> &'a T {
+        if cfg!(feature = "checked") {
+            let offset = self.offset();
+            assert!(
+                offset < self.len,
+                "read out of bounds: the offset is {offset} but the len is {}",
+                self.len
+            );
+        }
+        unsafe { &*self.ptr }
+    }
+}
+
+impl<T> Clone for Ptr<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Ptr<'_, T> {}
+
+impl<T> PartialEq for Ptr<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl<T> PartialOrd for Ptr<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.ptr.partial_cmp(&other.ptr)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Ptr, Unchecked};
+
+    #[test]
+    fn accesses_in_bounds() {
+        let mut values = vec![1, 2, 3];
+        unsafe {
+            *values.at_mut(1) += 10;
+            assert_eq!(*values.at(1), 12);
+        }
+
+        let ptr = Ptr::new(&values[..]).wrapping_add(2);
+        assert_eq!(ptr.offset(), 2);
+        assert_eq!(unsafe { *ptr.read() }, 3);
+        assert!(ptr < ptr.end());
+        assert!(ptr.wrapping_add(1) == ptr.end());
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn checks_indices() {
+        let values = [1, 2, 3];
+        unsafe { values.at(3) };
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    #[should_panic(expected = "read out of bounds")]
+    fn checks_pointers() {
+        let values = [1, 2, 3];
+        let ptr = Ptr::new(&values[..]).wrapping_add(5);
+        unsafe { ptr.read() };
+    }
+}